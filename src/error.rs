@@ -32,6 +32,12 @@ pub enum Error {
     VertexAlreadyExists,
     #[error("EdgeAlreadyExists")]
     EdgeAlreadyExists,
+    #[error("EdgeWasNotFound")]
+    EdgeWasNotFound,
+    #[error("WouldCreateCycle")]
+    WouldCreateCycle,
+    #[error("CycleDetected")]
+    CycleDetected,
     #[error("NullPointer")]
     NullPointer,
     #[error("MismatchedVicinity")]
@@ -20,7 +20,7 @@ where
     K: Ord,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.key.cmp(&other.key))
+        Some(self.cmp(other))
     }
 }
 
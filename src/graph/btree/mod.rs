@@ -54,6 +54,67 @@ where
         }
     }
 
+    pub fn len(&self) -> usize {
+        match self.root {
+            Some(ref root) => root.len(),
+            None => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn height(&self) -> usize {
+        match self.root {
+            Some(ref root) => root.height(),
+            None => 0,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        match self.root {
+            Some(ref root) => root.node_count(),
+            None => 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    /// Rebuilds the tree from the remaining pairs, so a removal costs
+    /// O(n log n) rather than O(log n). Prefer `retain` for bulk removals.
+    pub fn remove(&mut self, key: K) -> Result<V, Error> {
+        let mut pairs = self.into_vec();
+        let index = pairs
+            .iter()
+            .position(|(k, _)| C::compare(k, &key).is_eq())
+            .ok_or(Error::KeyWasNotFound)?;
+        let (_, value) = pairs.remove(index);
+
+        self.root = None;
+        for (key, value) in pairs {
+            self.insert(key, value)?;
+        }
+        Ok(value)
+    }
+
+    /// Keeps the pairs for which `f` returns true, rebuilding the tree once,
+    /// and returns the removed pairs.
+    pub fn retain(&mut self, mut f: impl FnMut(&K, &V) -> bool) -> Result<Vec<(K, V)>, Error> {
+        let (kept, removed): (Vec<_>, Vec<_>) = self
+            .into_vec()
+            .into_iter()
+            .partition(|(key, value)| f(key, value));
+
+        self.root = None;
+        for (key, value) in kept {
+            self.insert(key, value)?;
+        }
+        Ok(removed)
+    }
+
     #[allow(clippy::only_used_in_recursion)]
     fn search_node<'a>(
         &self,
@@ -82,7 +143,7 @@ where
     }
 
     pub fn search(&self, key: K) -> Result<&V, Error> {
-        let (node, at) = self.search_node(self.root.as_ref().unwrap(), key)?;
+        let (node, at) = self.search_node(self.root.as_ref().ok_or(Error::KeyWasNotFound)?, key)?;
 
         match node.node_type {
             NodeType::Internal(ref pairs, _) => Ok(&pairs.get(at).unwrap().value),
@@ -289,3 +350,24 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::definitions::Comp;
+
+    #[test]
+    fn height_stays_logarithmic() {
+        let mut tree: BTree<u32, (), Comp> = BTree::new();
+        for key in 0..1000 {
+            tree.insert(key, ()).unwrap();
+        }
+
+        assert_eq!(tree.len(), 1000);
+        // With minimum degree 2 every node but the root holds at least one key.
+        let height = tree.height();
+        assert!((5..=10).contains(&height));
+        assert!(tree.node_count() <= 1000);
+    }
+}
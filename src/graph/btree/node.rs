@@ -41,38 +41,59 @@ where
 
     #[allow(clippy::wrong_self_convention)]
     pub fn into_vec(&self) -> Vec<(K, V)> {
-        let self_keys = match &self.node_type {
+        let mut pairs: Vec<(K, V)> = Vec::with_capacity(self.len());
+        self.collect_in_order(&mut pairs);
+        pairs
+    }
+
+    fn collect_in_order(&self, acc: &mut Vec<(K, V)>) {
+        match &self.node_type {
             NodeType::Internal(key_val, children) => {
-                let all_children: Vec<Vec<(K, V)>> =
-                    children.iter().map(|node| node.into_vec()).collect();
-                let mut res: Vec<(K, V)> = Vec::default();
-                for child in all_children {
-                    res = [res.clone(), child].concat();
-                }
-                let to_be_inserted: Vec<(K, V)> = key_val
-                    .iter()
-                    .map(|kv| (kv.key.clone(), kv.value.clone()))
-                    .collect();
-                for (key, value) in to_be_inserted {
-                    let mut pos = 0;
-                    let mut idx = 0;
-                    res.iter().for_each(|(res_key, _)| {
-                        if key > *res_key {
-                            pos = idx + 1;
-                        }
-                        idx += 1;
-                    });
-                    res.insert(pos, (key, value));
+                for (index, child) in children.iter().enumerate() {
+                    child.collect_in_order(acc);
+                    if let Some(kv) = key_val.get(index) {
+                        acc.push((kv.key.clone(), kv.value.clone()));
+                    }
                 }
-                res
             }
-            NodeType::Leaf(keys) => keys
-                .iter()
-                .map(|kv| (kv.key.clone(), kv.value.clone()))
-                .collect(),
+            NodeType::Leaf(keys) => {
+                acc.extend(keys.iter().map(|kv| (kv.key.clone(), kv.value.clone())))
+            }
             NodeType::Undefined => panic!("Shouldn't happen"),
-        };
-        self_keys
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.node_type {
+            NodeType::Internal(pairs, children) => {
+                pairs.len() + children.iter().map(|child| child.len()).sum::<usize>()
+            }
+            NodeType::Leaf(pairs) => pairs.len(),
+            NodeType::Undefined => 0,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match &self.node_type {
+            NodeType::Internal(_, children) => {
+                1 + children.first().map_or(0, |child| child.height())
+            }
+            NodeType::Leaf(_) => 1,
+            NodeType::Undefined => 0,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        match &self.node_type {
+            NodeType::Internal(_, children) => {
+                1 + children
+                    .iter()
+                    .map(|child| child.node_count())
+                    .sum::<usize>()
+            }
+            NodeType::Leaf(_) => 1,
+            NodeType::Undefined => 0,
+        }
     }
 
     pub fn split(&mut self, t: usize) -> Result<Split<K, V>, Error> {
@@ -165,3 +186,25 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{btree::BTree, definitions::Comp};
+
+    #[test]
+    fn into_vec_is_sorted_for_multi_level_trees() {
+        let mut tree: BTree<u64, u64, Comp> = BTree::new();
+        // Linear congruential sequence visiting every key below 512 once.
+        let mut key = 7;
+        for _ in 0..512 {
+            key = (key * 5 + 3) % 512;
+            tree.insert(key, key * 2).unwrap();
+        }
+        assert!(tree.height() > 2);
+
+        let pairs = tree.into_vec();
+        assert_eq!(pairs.len(), 512);
+        assert!(pairs.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(pairs.iter().all(|(key, value)| *value == key * 2));
+    }
+}
@@ -0,0 +1,145 @@
+use super::{
+    definitions::{Comp, Vicinity, WithBoth, WithIngoing, WithOutgoing},
+    Comparator, Graph,
+};
+use crate::Error;
+use std::marker::PhantomData;
+
+pub struct GraphBuilder<V, E, Id, S = WithBoth, C = Comp>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    vertices: Vec<(Id, V)>,
+    edges: Vec<(E, Id, Id)>,
+    state: PhantomData<(S, C)>,
+}
+
+impl<V, E, Id, S, C> GraphBuilder<V, E, Id, S, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    pub(super) fn new() -> Self {
+        Self {
+            vertices: Vec::default(),
+            edges: Vec::default(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn vertex(mut self, id: Id, info: V) -> Self {
+        self.vertices.push((id, info));
+        self
+    }
+
+    pub fn edge(mut self, info: E, start: Id, end: Id) -> Self {
+        self.edges.push((info, start, end));
+        self
+    }
+
+    fn add_edges(edges: Vec<(E, Id, Id)>, graph: &mut Graph<V, E, Id, S, C>) -> Result<(), Error> {
+        for (info, start, end) in edges {
+            graph.add_edge(info, start, end)?;
+        }
+        Ok(())
+    }
+}
+
+impl<V, E, Id, C> GraphBuilder<V, E, Id, WithOutgoing, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy + Clone,
+    C: Comparator<Id>,
+{
+    pub fn build(self) -> Result<Graph<V, E, Id, WithOutgoing, C>, Error> {
+        let mut graph: Graph<V, E, Id, WithOutgoing, C> = Graph::new();
+        for (id, info) in self.vertices {
+            graph.add_vertex(id, info, Vicinity::Outgoing { edges: None })?;
+        }
+        Self::add_edges(self.edges, &mut graph)?;
+        Ok(graph)
+    }
+}
+
+impl<V, E, Id, C> GraphBuilder<V, E, Id, WithIngoing, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    pub fn build(self) -> Result<Graph<V, E, Id, WithIngoing, C>, Error> {
+        let mut graph: Graph<V, E, Id, WithIngoing, C> = Graph::new();
+        for (id, info) in self.vertices {
+            graph.add_vertex(id, info, Vicinity::Ingoing { edges: None })?;
+        }
+        Self::add_edges(self.edges, &mut graph)?;
+        Ok(graph)
+    }
+}
+
+impl<V, E, Id, C> GraphBuilder<V, E, Id, WithBoth, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    pub fn build(self) -> Result<Graph<V, E, Id, WithBoth, C>, Error> {
+        let mut graph: Graph<V, E, Id, WithBoth, C> = Graph::new();
+        for (id, info) in self.vertices {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            graph.add_vertex(id, info, vicinity)?;
+        }
+        Self::add_edges(self.edges, &mut graph)?;
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_matches_imperative_construction() {
+        let built = Graph::<&str, u32, u32, WithBoth>::builder()
+            .vertex(1, "a")
+            .vertex(2, "b")
+            .edge(5, 1, 2)
+            .edge(7, 2, 2)
+            .build()
+            .unwrap();
+
+        let mut graph: Graph<&str, u32, u32, WithBoth> = Graph::new();
+        for (id, info) in [(1, "a"), (2, "b")] {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            graph.add_vertex(id, info, vicinity).unwrap();
+        }
+        graph.add_edge(5, 1, 2).unwrap();
+        graph.add_edge(7, 2, 2).unwrap();
+
+        assert_eq!(built, graph);
+    }
+
+    #[test]
+    fn builder_surfaces_first_error() {
+        let result = Graph::<(), (), u32, WithOutgoing>::builder()
+            .vertex(1, ())
+            .edge((), 1, 1)
+            .edge((), 1, 2)
+            .build();
+        assert!(matches!(result, Err(Error::KeyWasNotFound)));
+    }
+}
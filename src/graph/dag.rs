@@ -0,0 +1,101 @@
+use super::{
+    definitions::{Comp, WithOutgoing},
+    Comparator, Graph,
+};
+use crate::Error;
+use std::{collections::VecDeque, ops::Deref};
+
+/// A `WithOutgoing` graph known to be acyclic. Only shared access to the
+/// inner graph is exposed, so the invariant cannot be broken after
+/// construction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dag<V, E, Id, C = Comp>(Graph<V, E, Id, WithOutgoing, C>)
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>;
+
+impl<V, E, Id, C> Dag<V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy + Clone,
+    C: Comparator<Id>,
+{
+    pub fn topological_sort(&self) -> VecDeque<Id> {
+        self.0.topological_sort_sorted().expect("dag is acyclic")
+    }
+
+    pub fn topological_edge_order(&self) -> Vec<(Id, Id)> {
+        self.0.topological_edge_order().expect("dag is acyclic")
+    }
+
+    pub fn into_inner(self) -> Graph<V, E, Id, WithOutgoing, C> {
+        self.0
+    }
+}
+
+impl<V, E, Id, C> TryFrom<Graph<V, E, Id, WithOutgoing, C>> for Dag<V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy + Clone,
+    C: Comparator<Id>,
+{
+    type Error = Error;
+
+    fn try_from(graph: Graph<V, E, Id, WithOutgoing, C>) -> Result<Self, Self::Error> {
+        graph.topological_sort_sorted()?;
+        Ok(Self(graph))
+    }
+}
+
+impl<V, E, Id, C> Deref for Dag<V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    type Target = Graph<V, E, Id, WithOutgoing, C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Digraph = Graph<(), (), u32, WithOutgoing>;
+
+    #[test]
+    fn try_from_checks_acyclicity() {
+        let cyclic = Digraph::builder()
+            .vertex(1, ())
+            .vertex(2, ())
+            .vertex(3, ())
+            .edge((), 1, 2)
+            .edge((), 2, 3)
+            .edge((), 3, 1)
+            .build()
+            .unwrap();
+        assert!(Dag::try_from(cyclic).is_err());
+
+        let acyclic = Digraph::builder()
+            .vertex(1, ())
+            .vertex(2, ())
+            .vertex(3, ())
+            .edge((), 2, 3)
+            .edge((), 1, 2)
+            .edge((), 1, 3)
+            .build()
+            .unwrap();
+        let dag = Dag::try_from(acyclic).unwrap();
+        assert_eq!(dag.topological_sort(), vec![1, 2, 3]);
+        assert_eq!(dag.topological_edge_order().len(), 3);
+        assert_eq!(dag.vertex_count(), 3);
+    }
+}
@@ -1,11 +1,14 @@
 use std::{
     cell::RefCell,
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     fmt::Debug,
+    hash::Hash,
     rc::{Rc, Weak},
 };
 
 pub mod path;
+pub mod style;
+pub mod weight;
 
 use crate::graph::btree::key_value::Comparator;
 
@@ -17,6 +20,13 @@ pub struct Observer<T>(pub Weak<RefCell<T>>);
 pub type VertexFn<V, E, Id, R> = Box<dyn Fn(&Vertex<V, E, Id>) -> R>;
 pub type VertexFnMut<V, E, Id, R> = Box<dyn Fn(&mut Vertex<V, E, Id>) -> R>;
 
+pub trait Visitor<V, E, Id>
+where
+    E: Clone,
+{
+    fn visit(&mut self, vertex: &Vertex<V, E, Id>);
+}
+
 impl<V, E, Id> Debug for Observer<Vertex<V, E, Id>>
 where
     E: Clone + Debug,
@@ -62,6 +72,46 @@ pub struct WithIngoing;
 #[derive(Debug, Clone, Default)]
 pub struct WithBoth;
 
+/// Implemented by the graph state markers to build the vicinity a fresh
+/// vertex starts with, for code that is generic over the state.
+pub trait State {
+    fn empty_vicinity<V, E: Clone, Id>() -> Vicinity<V, E, Id>;
+}
+
+impl State for WithOutgoing {
+    fn empty_vicinity<V, E: Clone, Id>() -> Vicinity<V, E, Id> {
+        Vicinity::Outgoing { edges: None }
+    }
+}
+
+impl State for WithIngoing {
+    fn empty_vicinity<V, E: Clone, Id>() -> Vicinity<V, E, Id> {
+        Vicinity::Ingoing { edges: None }
+    }
+}
+
+impl State for WithBoth {
+    fn empty_vicinity<V, E: Clone, Id>() -> Vicinity<V, E, Id> {
+        Vicinity::Both {
+            ingoing_edges: None,
+            outgoing_edges: None,
+        }
+    }
+}
+
+pub type AdjacencyMatrix<Id, T> = (Vec<Id>, Vec<Vec<T>>);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphDiff<Id>
+where
+    Id: Hash + Eq,
+{
+    pub added_vertices: HashSet<Id>,
+    pub removed_vertices: HashSet<Id>,
+    pub added_edges: HashSet<(Id, Id)>,
+    pub removed_edges: HashSet<(Id, Id)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Vertex<V, E, Id>
 where
@@ -82,47 +132,55 @@ where
         Self { id, info, vicinity }
     }
 
+    pub fn edges(&self) -> Vec<&Edge<V, E, Id>> {
+        match &self.vicinity {
+            Vicinity::Outgoing { edges } | Vicinity::Ingoing { edges } => {
+                edges.iter().flatten().collect()
+            }
+            Vicinity::Both {
+                ingoing_edges,
+                outgoing_edges,
+            } => ingoing_edges
+                .iter()
+                .chain(outgoing_edges.iter())
+                .flatten()
+                .collect(),
+        }
+    }
+
     pub fn is_in_cycle(&self) -> bool {
+        let successors = |vertex: &Vertex<V, E, Id>| -> Vec<Shared<Vertex<V, E, Id>>> {
+            match &vertex.vicinity {
+                Vicinity::Outgoing { edges: Some(edges) }
+                | Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: Some(edges),
+                } => edges
+                    .iter()
+                    .filter_map(|edge| edge.end.0.upgrade())
+                    .collect(),
+                Vicinity::Outgoing { edges: None }
+                | Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: None,
+                } => vec![],
+
+                _ => panic!("Shouldn't happent"),
+            }
+        };
+
         let mut discovered: Vec<Id> = Vec::default();
-        let mut queue: VecDeque<&Vertex<V, E, Id>> = VecDeque::default();
-        queue.push_back(self);
+        let mut queue: VecDeque<Shared<Vertex<V, E, Id>>> = successors(self).into();
 
-        while !queue.is_empty() {
-            let current = queue.pop_front().unwrap();
-            if discovered.contains(&current.id) {
+        while let Some(current) = queue.pop_front() {
+            let current = current.borrow();
+            if current.id == self.id {
+                return true;
+            } else if discovered.contains(&current.id) {
                 continue;
-            } else {
-                match &current.vicinity {
-                    Vicinity::Outgoing { edges: Some(edges) } => {
-                        for edge in edges {
-                            if edge.get_end_id() == self.id {
-                                return true;
-                            }
-                            queue.push_back(edge.borrow_end_vertex());
-                            discovered.push(current.id.clone());
-                        }
-                    }
-                    Vicinity::Both {
-                        ingoing_edges: _,
-                        outgoing_edges: Some(edges),
-                    } => {
-                        for edge in edges {
-                            if edge.get_end_id() == self.id {
-                                return true;
-                            }
-                            queue.push_back(edge.borrow_end_vertex());
-                            discovered.push(current.id.clone());
-                        }
-                    }
-                    Vicinity::Both {
-                        ingoing_edges: _,
-                        outgoing_edges: None,
-                    } => continue,
-                    Vicinity::Outgoing { edges: None } => continue,
-
-                    _ => panic!("Shouldn't happent"),
-                }
             }
+            discovered.push(current.id.clone());
+            queue.extend(successors(&current));
         }
         false
     }
@@ -136,33 +194,64 @@ where
     pub info: E,
     pub start: Observer<Vertex<V, E, Id>>,
     pub end: Observer<Vertex<V, E, Id>>,
+    start_id: Id,
+    end_id: Id,
 }
 
-impl<V, E, Id> PartialEq for Edge<V, E, Id>
+impl<V, E, Id> Edge<V, E, Id>
 where
     E: Clone,
-    Id: PartialEq,
 {
-    fn eq(&self, other: &Self) -> bool {
-        let binding = self.start.0.upgrade().unwrap();
-        let self_start = unsafe { &(*binding.as_ptr()).id };
+    /// An edge is dangling once either of its endpoints has been dropped from the graph.
+    pub fn is_dangling(&self) -> bool {
+        self.start.0.upgrade().is_none() || self.end.0.upgrade().is_none()
+    }
 
-        let binding = self.end.0.upgrade().unwrap();
-        let self_end = unsafe { &(*binding.as_ptr()).id };
+    /// Runs `f` on the start vertex while holding a shared `RefCell` borrow of it.
+    /// The edge only holds a weak reference, and the vertex may already have been
+    /// removed from the graph, so a dangling edge yields `None` instead of `R`.
+    pub fn with_start_vertex<R>(&self, f: impl FnOnce(&Vertex<V, E, Id>) -> R) -> Option<R> {
+        let binding = self.start.0.upgrade()?;
+        let vertex = binding.borrow();
+        Some(f(&vertex))
+    }
 
-        let binding = other.start.0.upgrade().unwrap();
-        let other_start = unsafe { &(*binding.as_ptr()).id };
+    /// Runs `f` on the end vertex while holding a shared `RefCell` borrow of it.
+    /// The edge only holds a weak reference, and the vertex may already have been
+    /// removed from the graph, so a dangling edge yields `None` instead of `R`.
+    pub fn with_end_vertex<R>(&self, f: impl FnOnce(&Vertex<V, E, Id>) -> R) -> Option<R> {
+        let binding = self.end.0.upgrade()?;
+        let vertex = binding.borrow();
+        Some(f(&vertex))
+    }
+}
 
-        let binding = other.end.0.upgrade().unwrap();
-        let other_end = unsafe { &(*binding.as_ptr()).id };
+impl<V, E, Id> Edge<V, E, Id>
+where
+    E: Clone,
+    Id: PartialEq,
+{
+    /// Compares only the endpoints of two edges, ignoring their `info`.
+    pub fn same_endpoints(&self, other: &Self) -> bool {
+        self.start_id == other.start_id && self.end_id == other.end_id
+    }
+}
 
-        (self_start == other_start) && (self_end == other_end)
+/// Two edges are equal when they connect the same endpoints and carry equal `info`,
+/// so parallel edges of a multigraph are told apart. Use `same_endpoints` to ignore `info`.
+impl<V, E, Id> PartialEq for Edge<V, E, Id>
+where
+    E: Clone + PartialEq,
+    Id: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.same_endpoints(other) && self.info == other.info
     }
 }
 
 impl<V, E, Id> Eq for Edge<V, E, Id>
 where
-    E: Clone,
+    E: Clone + Eq,
     Id: PartialEq,
 {
 }
@@ -174,23 +263,38 @@ where
     Id: Clone,
 {
     pub fn new(info: E, start: Shared<Vertex<V, E, Id>>, end: Shared<Vertex<V, E, Id>>) -> Self {
+        let (start_id, end_id) = (start.borrow().id.clone(), end.borrow().id.clone());
         Self {
             info,
             start: Observer(Rc::downgrade(&start)),
             end: Observer(Rc::downgrade(&end)),
+            start_id,
+            end_id,
         }
     }
 
+    /// Endpoint ids recorded when the edge was created, still available once
+    /// the edge is dangling.
+    pub fn endpoint_ids(&self) -> (Id, Id) {
+        (self.start_id.clone(), self.end_id.clone())
+    }
+
+    pub fn start_vertex_id(&self) -> Id {
+        self.start_id.clone()
+    }
+
+    pub fn end_vertex_id(&self) -> Id {
+        self.end_id.clone()
+    }
+
+    /// Does not touch the start vertex, so it is safe on dangling edges.
     pub fn get_start_id(&self) -> Id {
-        let binding = self.start.0.upgrade().unwrap();
-        let binding = binding.borrow();
-        binding.id.clone()
+        self.start_id.clone()
     }
 
+    /// Does not touch the end vertex, so it is safe on dangling edges.
     pub fn get_end_id(&self) -> Id {
-        let binding = self.end.0.upgrade().unwrap();
-        let binding = binding.borrow();
-        binding.id.clone()
+        self.end_id.clone()
     }
 
     pub fn get_start_info(&self) -> V {
@@ -216,37 +320,49 @@ where
         let binding = binding.borrow();
         (binding.id.clone(), binding.info.clone())
     }
+}
 
-    pub fn borrow_start_vertex(&self) -> &Vertex<V, E, Id> {
-        let binding = self.start.0.upgrade().unwrap();
-        let vertex_ptr = binding.as_ptr();
-        unsafe { &(*vertex_ptr) }
-    }
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Comp {}
 
-    pub fn borrow_end_vertex(&self) -> &Vertex<V, E, Id> {
-        let binding = self.end.0.upgrade().unwrap();
-        let vertex_ptr = binding.as_ptr();
-        unsafe { &(*vertex_ptr) }
+impl<Id: Ord> Comparator<Id> for Comp {
+    fn compare(lhs: &Id, rhs: &Id) -> std::cmp::Ordering {
+        lhs.cmp(rhs)
     }
+}
 
-    pub fn borrow_start_vertex_mut(&mut self) -> &mut Vertex<V, E, Id> {
-        let binding = self.start.0.upgrade().unwrap();
-        let vertex_ptr = binding.as_ptr();
-        unsafe { &mut (*vertex_ptr) }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    pub fn borrow_end_vertex_mut(&mut self) -> &mut Vertex<V, E, Id> {
-        let binding = self.end.0.upgrade().unwrap();
-        let vertex_ptr = binding.as_ptr();
-        unsafe { &mut (*vertex_ptr) }
+    fn vertex(id: u32) -> Shared<Vertex<(), u32, u32>> {
+        let vicinity = Vicinity::Outgoing { edges: None };
+        Rc::new(RefCell::new(Vertex::new(id, (), vicinity)))
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-pub struct Comp {}
+    #[test]
+    fn parallel_edges_with_different_info_are_unequal() {
+        let (a, b) = (vertex(1), vertex(2));
+        let light = Edge::new(1, Rc::clone(&a), Rc::clone(&b));
+        let heavy = Edge::new(5, Rc::clone(&a), Rc::clone(&b));
 
-impl<Id: Ord> Comparator<Id> for Comp {
-    fn compare(lhs: &Id, rhs: &Id) -> std::cmp::Ordering {
-        lhs.cmp(rhs)
+        assert!(light.same_endpoints(&heavy));
+        assert!(light != heavy);
+        assert!(light == light.clone());
+    }
+
+    #[test]
+    fn scoped_accessors_survive_dangling_edges() {
+        let (a, b) = (vertex(1), vertex(2));
+        let edge = Edge::new(7, Rc::clone(&a), Rc::clone(&b));
+        let copy = edge.clone();
+        assert_eq!(edge.with_start_vertex(|vertex| vertex.id), Some(1));
+        assert_eq!((edge.start_vertex_id(), edge.end_vertex_id()), (1, 2));
+
+        drop(b);
+        assert!(edge.is_dangling());
+        assert_eq!(edge.with_end_vertex(|vertex| vertex.id), None);
+        assert_eq!(edge.end_vertex_id(), 2);
+        assert!(edge == copy);
     }
 }
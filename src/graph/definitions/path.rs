@@ -4,7 +4,7 @@ use itertools::*;
 
 use crate::Error;
 
-use super::Edge;
+use super::{weight::Weight, Edge};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Unit(pub ());
@@ -77,11 +77,31 @@ where
     Id: Clone,
 {
     pub fn start_with(&self) -> Id {
-        self.0.get(0).expect("Path is empty").get_start_id()
+        self.0.first().expect("Path is empty").get_start_id()
     }
 
     pub fn ends_with(&self) -> Id {
-        self.0.get(0).expect("Path is empty").get_end_id()
+        self.0.last().expect("Path is empty").get_end_id()
+    }
+
+    /// Sums edge weights; an empty path weighs the default (zero) value.
+    pub fn total_weight(&self) -> E::Output
+    where
+        E: Weight,
+    {
+        self.0
+            .iter()
+            .fold(E::Output::default(), |acc, edge| acc + edge.info.weight())
+    }
+
+    /// Incomparable weights (e.g. NaN) are treated as equal.
+    pub fn cmp_by_weight(&self, other: &Self) -> std::cmp::Ordering
+    where
+        E: Weight,
+    {
+        self.total_weight()
+            .partial_cmp(&other.total_weight())
+            .unwrap_or(std::cmp::Ordering::Equal)
     }
 }
 
@@ -96,7 +116,7 @@ where
         if edges.is_empty() {
             return false;
         }
-        for edge in edges {
+        for edge in edges.iter().filter(|edge| !edge.is_dangling()) {
             if edge.get_start_id() == id || edge.get_end_id() == id {
                 return true;
             }
@@ -104,10 +124,29 @@ where
         false
     }
 
+    pub fn try_concat(&self, other: &Path<V, E, Id>) -> Result<Path<V, E, Id>, Error> {
+        if self.0.is_empty() {
+            return Ok(other.clone());
+        } else if other.0.is_empty() {
+            return Ok(self.clone());
+        } else if self.0.last().is_some_and(|edge| edge.is_dangling())
+            || other.0.first().is_some_and(|edge| edge.is_dangling())
+        {
+            return Err(Error::NullPointer);
+        }
+
+        if self.ends_with() != other.start_with() {
+            return Err(Error::WithMessage("paths not connected"));
+        }
+        Ok(Path([&self.0[..], &other.0[..]].concat()))
+    }
+
     pub fn subpath_between(&self, start: Id, end: Id) -> Result<Path<V, E, Id>, Error> {
         let edges = &self.0;
         if edges.is_empty() {
             return Err(Error::KeyWasNotFound);
+        } else if edges.iter().any(|edge| edge.is_dangling()) {
+            return Err(Error::NullPointer);
         }
 
         let (abs_start, abs_end) = (self.start_with(), self.ends_with());
@@ -138,7 +177,7 @@ where
 
 impl<V, E, Id> PartialEq for Path<V, E, Id>
 where
-    E: Clone,
+    E: Clone + PartialEq,
     V: Clone,
     Id: PartialEq,
 {
@@ -154,7 +193,7 @@ where
 
 impl<V, E, Id> Eq for Path<V, E, Id>
 where
-    E: Clone,
+    E: Clone + Eq,
     V: Clone,
     Id: PartialEq + Eq,
 {
@@ -176,16 +215,18 @@ where
         if self_end.is_none() {
             return self;
         }
-        let binding = self_end.unwrap().end.0.upgrade();
-        let binding = binding.unwrap();
+        let Some(binding) = self_end.unwrap().end.0.upgrade() else {
+            return self;
+        };
         let self_end = &binding.borrow().id;
 
-        let other_start = rhs.0.last();
+        let other_start = rhs.0.first();
         if other_start.is_none() {
             return self;
         }
-        let binding = other_start.unwrap().start.0.upgrade();
-        let binding = binding.unwrap();
+        let Some(binding) = other_start.unwrap().start.0.upgrade() else {
+            return self;
+        };
         let other_start = &binding.borrow().id;
 
         match self_end.cmp(other_start) {
@@ -195,29 +236,38 @@ where
     }
 }
 
-impl<V, E, Id> From<Path<V, E, Id>> for Vec<(V, E)>
+impl<V, E, Id> TryFrom<Path<V, E, Id>> for Vec<(V, E)>
 where
     E: Clone,
     V: Clone,
     Id: Clone,
 {
-    fn from(value: Path<V, E, Id>) -> Self {
+    type Error = Error;
+
+    fn try_from(value: Path<V, E, Id>) -> Result<Self, Self::Error> {
+        if value.0.iter().any(|edge| edge.is_dangling()) {
+            return Err(Error::NullPointer);
+        }
+        let Some(last) = value.0.last() else {
+            return Ok(vec![]);
+        };
+
         let mut result = value
             .0
             .iter()
-            .map(|edge| (edge.borrow_start_vertex().info.clone(), edge.info.clone()))
+            .map(|edge| (edge.get_start_info(), edge.info.clone()))
             .collect::<Vec<(V, E)>>();
         result.push((
-            value.0.last().unwrap().borrow_end_vertex().info.clone(),
-            value.0.last().unwrap().info.clone(), // unneccassary edge info
+            last.get_end_info(),
+            last.info.clone(), // unneccassary edge info
         ));
-        result
+        Ok(result)
     }
 }
 
 impl<V, E, Id> PartialEq for Paths<V, E, Id>
 where
-    E: Clone,
+    E: Clone + PartialEq,
     V: Clone,
     Id: PartialEq + Eq + Clone,
 {
@@ -267,3 +317,79 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Shared, Vertex, Vicinity};
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    fn vertices(count: u32) -> Vec<Shared<Vertex<(), u32, u32>>> {
+        (0..count)
+            .map(|id| {
+                let vicinity = Vicinity::Outgoing { edges: None };
+                Rc::new(RefCell::new(Vertex::new(id, (), vicinity)))
+            })
+            .collect()
+    }
+
+    fn path(vertices: &[Shared<Vertex<(), u32, u32>>], ids: &[usize]) -> Path<(), u32, u32> {
+        Path(
+            ids.windows(2)
+                .map(|pair| {
+                    let (start, end) = (&vertices[pair[0]], &vertices[pair[1]]);
+                    Edge::new(1, Rc::clone(start), Rc::clone(end))
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn concat_joinable_paths() {
+        let vertices = vertices(4);
+        let (lhs, rhs) = (path(&vertices, &[0, 1]), path(&vertices, &[1, 2, 3]));
+
+        let joined = lhs.try_concat(&rhs).unwrap();
+        assert_eq!((joined.start_with(), joined.ends_with()), (0, 3));
+        assert_eq!(joined.0.len(), 3);
+        assert_eq!((lhs + rhs).0.len(), 3);
+    }
+
+    #[test]
+    fn concat_disjoint_paths() {
+        let vertices = vertices(4);
+        let (lhs, rhs) = (path(&vertices, &[0, 1]), path(&vertices, &[2, 3]));
+
+        assert!(matches!(
+            lhs.try_concat(&rhs),
+            Err(Error::WithMessage("paths not connected"))
+        ));
+        assert_eq!((lhs + rhs).0.len(), 1);
+    }
+
+    #[test]
+    fn sort_paths_by_weight() {
+        let vertices = vertices(3);
+        let weighted = |weights: &[u32]| -> Path<(), u32, u32> {
+            Path(
+                weights
+                    .iter()
+                    .map(|&weight| {
+                        Edge::new(weight, Rc::clone(&vertices[0]), Rc::clone(&vertices[1]))
+                    })
+                    .collect(),
+            )
+        };
+
+        let mut paths = [
+            weighted(&[4, 4]),
+            weighted(&[]),
+            weighted(&[1, 2, 3]),
+            weighted(&[5]),
+        ];
+        paths.sort_by(Path::cmp_by_weight);
+        let totals: Vec<u32> = paths.iter().map(Path::total_weight).collect();
+        assert_eq!(totals, vec![0, 5, 6, 8]);
+        assert_eq!(paths[1].0.len(), 1);
+    }
+}
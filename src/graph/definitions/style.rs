@@ -0,0 +1,49 @@
+pub use dot_writer::{Color, RankDirection, Shape};
+
+#[derive(Debug, Clone)]
+pub struct GraphStyle {
+    pub font: String,
+    pub node_shape: Shape,
+    pub node_color: Color,
+    pub edge_color: Color,
+    pub background_color: Color,
+    pub rankdir: RankDirection,
+}
+
+impl Default for GraphStyle {
+    fn default() -> Self {
+        Self {
+            font: "FiraCode Mono Nerd Font".to_string(),
+            node_shape: Shape::Circle,
+            node_color: Color::LightGrey,
+            edge_color: Color::White,
+            background_color: Color::Gray20,
+            rankdir: RankDirection::TopBottom,
+        }
+    }
+}
+
+impl GraphStyle {
+    pub fn set_rankdir(&mut self, rankdir: RankDirection) -> &mut Self {
+        self.rankdir = rankdir;
+        self
+    }
+}
+
+/// Wraps `value` in double quotes, escaping the characters DOT treats specially
+/// inside a quoted string.
+pub(crate) fn quote_dot(value: &str) -> String {
+    let mut quoted = value
+        .chars()
+        .fold(String::with_capacity(value.len() + 2), |mut acc, c| {
+            match c {
+                '"' => acc.push_str("\\\""),
+                '\\' => acc.push_str("\\\\"),
+                _ => acc.push(c),
+            }
+            acc
+        });
+    quoted.insert(0, '"');
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,121 @@
+use std::{fmt::Debug, ops::Add};
+
+pub trait Measure: Copy + Debug + Default + PartialOrd + Add<Output = Self> {
+    fn as_f64(self) -> f64;
+}
+
+pub trait Weight {
+    type Output: Measure;
+
+    fn weight(&self) -> Self::Output;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                fn as_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+
+            impl Weight for $t {
+                type Output = $t;
+
+                fn weight(&self) -> Self::Output {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// Lets edges carry a weight alongside other data, e.g. `(f64, String)` for a
+/// cost plus a name. Weighted algorithms only look at the first field.
+impl<W, T> Weight for (W, T)
+where
+    W: Weight,
+{
+    type Output = W::Output;
+
+    fn weight(&self) -> Self::Output {
+        self.0.weight()
+    }
+}
+
+pub type WeightedPath<Id, W> = (Vec<Id>, W);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::graph::{Graph, Vicinity, WithOutgoing};
+
+    #[derive(Clone)]
+    struct Road {
+        km: u32,
+    }
+
+    impl Weight for Road {
+        type Output = u32;
+
+        fn weight(&self) -> Self::Output {
+            self.km
+        }
+    }
+
+    #[test]
+    fn custom_edges_expose_their_weight() {
+        let mut graph = Graph::<(), Road, u32, WithOutgoing>::new();
+        for id in [1, 2, 3] {
+            graph
+                .add_vertex(id, (), Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge(Road { km: 10 }, 1, 3).unwrap();
+        graph.add_edge(Road { km: 3 }, 1, 2).unwrap();
+        graph.add_edge(Road { km: 4 }, 2, 3).unwrap();
+
+        let total = graph
+            .edges_iter()
+            .map(|(_, _, road)| road.weight())
+            .fold(u32::default(), |acc, km| acc + km);
+        assert_eq!(total, 17);
+        assert_eq!(7u8.weight(), 7);
+        assert_eq!(2.5f64.weight().as_f64(), 2.5);
+    }
+
+    #[test]
+    fn shortest_path_over_custom_weight() {
+        let mut graph = Graph::<(), Road, u32, WithOutgoing>::new();
+        for id in [1, 2, 3] {
+            graph
+                .add_vertex(id, (), Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge(Road { km: 10 }, 1, 3).unwrap();
+        graph.add_edge(Road { km: 3 }, 1, 2).unwrap();
+        graph.add_edge(Road { km: 4 }, 2, 3).unwrap();
+
+        assert_eq!(graph.shortest_path(1, 3).unwrap(), Some((vec![1, 2, 3], 7)));
+    }
+
+    #[test]
+    fn dijkstra_over_labeled_weights() {
+        let graph = Graph::<(), (u32, &str), u32, WithOutgoing>::builder()
+            .vertex(1, ())
+            .vertex(2, ())
+            .vertex(3, ())
+            .edge((2, "ferry"), 1, 2)
+            .edge((2, "bridge"), 2, 3)
+            .edge((9, "highway"), 1, 3)
+            .build()
+            .unwrap();
+
+        assert_eq!((7, "toll").weight(), 7);
+        assert_eq!(graph.shortest_path(1, 3).unwrap(), Some((vec![1, 2, 3], 4)));
+        assert_eq!(graph.get_edge_info(2, 3).unwrap().1, "bridge");
+    }
+}
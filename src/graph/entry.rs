@@ -0,0 +1,131 @@
+use super::{
+    definitions::{Comp, Shared, Vertex, Vicinity, WithOutgoing},
+    Comparator, Graph,
+};
+use crate::Error;
+use std::cell::RefMut;
+
+/// A view into a single edge of a `WithOutgoing` graph, obtained from
+/// `Graph::edge_entry`. With parallel edges the first one is used.
+pub enum EdgeEntry<'a, V, E, Id, C = Comp>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    Occupied(RefMut<'a, E>),
+    Vacant(VacantEdge<'a, V, E, Id, C>),
+}
+
+pub struct VacantEdge<'a, V, E, Id, C = Comp>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    graph: &'a mut Graph<V, E, Id, WithOutgoing, C>,
+    start: Id,
+    end: Id,
+}
+
+impl<'a, V, E, Id, C> EdgeEntry<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy + Clone,
+    C: Comparator<Id>,
+{
+    pub fn or_insert(self, info: E) -> Result<RefMut<'a, E>, Error> {
+        match self {
+            EdgeEntry::Occupied(info) => Ok(info),
+            EdgeEntry::Vacant(vacant) => vacant.insert(info),
+        }
+    }
+
+    pub fn or_insert_with(self, info: impl FnOnce() -> E) -> Result<RefMut<'a, E>, Error> {
+        match self {
+            EdgeEntry::Occupied(info) => Ok(info),
+            EdgeEntry::Vacant(vacant) => vacant.insert(info()),
+        }
+    }
+
+    pub fn and_modify(mut self, f: impl FnOnce(&mut E)) -> Self {
+        if let EdgeEntry::Occupied(info) = &mut self {
+            f(info);
+        }
+        self
+    }
+}
+
+impl<'a, V, E, Id, C> VacantEdge<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy + Clone,
+    C: Comparator<Id>,
+{
+    pub(super) fn new(graph: &'a mut Graph<V, E, Id, WithOutgoing, C>, start: Id, end: Id) -> Self {
+        Self { graph, start, end }
+    }
+
+    pub fn insert(self, info: E) -> Result<RefMut<'a, E>, Error> {
+        self.graph.add_edge(info, self.start, self.end)?;
+        let graph: &'a Graph<V, E, Id, WithOutgoing, C> = self.graph;
+        find_info(graph.vertices.search(self.start)?, self.end).ok_or(Error::EdgeWasNotFound)
+    }
+}
+
+pub(super) fn find_info<V, E, Id>(
+    vertex: &Shared<Vertex<V, E, Id>>,
+    end: Id,
+) -> Option<RefMut<'_, E>>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialEq + Clone,
+{
+    RefMut::filter_map(vertex.borrow_mut(), |vertex| match &mut vertex.vicinity {
+        Vicinity::Outgoing { edges: Some(edges) } => edges
+            .iter_mut()
+            .filter(|edge| !edge.is_dangling())
+            .find(|edge| edge.get_end_id() == end)
+            .map(|edge| &mut edge.info),
+        _ => None,
+    })
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn occupied_and_vacant_entries() {
+        let mut graph = Graph::<(), u32, u32, WithOutgoing>::builder()
+            .vertex(1, ())
+            .vertex(2, ())
+            .edge(3, 1, 2)
+            .build()
+            .unwrap();
+
+        *graph.edge_entry(1, 2).unwrap().or_insert(0).unwrap() += 1;
+        graph
+            .edge_entry(1, 2)
+            .unwrap()
+            .and_modify(|info| *info *= 10);
+        assert_eq!(graph.get_edge_info(1, 2).unwrap(), 40);
+
+        *graph.edge_entry(2, 1).unwrap().or_insert(5).unwrap() += 1;
+        assert_eq!(graph.get_edge_info(2, 1).unwrap(), 6);
+
+        *graph
+            .edge_entry(1, 1)
+            .unwrap()
+            .or_insert_with(|| 8)
+            .unwrap() += 1;
+        assert_eq!(graph.get_edge_info(1, 1).unwrap(), 9);
+        assert!(graph.edge_entry(1, 4).is_err());
+    }
+}
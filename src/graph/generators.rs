@@ -0,0 +1,85 @@
+use super::{Graph, Vicinity, WithOutgoing};
+
+pub type Generated = Graph<(), (), usize, WithOutgoing>;
+
+/// Directed G(n, p): every ordered pair of distinct vertices gets an edge with
+/// probability `p`. The same `seed` always yields the same graph.
+pub fn erdos_renyi(n: usize, p: f64, seed: u64) -> Generated {
+    let mut rng = SplitMix64(seed);
+    from_edges(
+        n,
+        (0..n)
+            .flat_map(|start| (0..n).map(move |end| (start, end)))
+            .filter(|(start, end)| start != end)
+            .filter(|_| rng.next_f64() < p)
+            .collect(),
+    )
+}
+
+pub fn complete_graph(n: usize) -> Generated {
+    from_edges(
+        n,
+        (0..n)
+            .flat_map(|start| (0..n).map(move |end| (start, end)))
+            .filter(|(start, end)| start != end)
+            .collect(),
+    )
+}
+
+pub fn cycle_graph(n: usize) -> Generated {
+    if n < 2 {
+        return from_edges(n, vec![]);
+    }
+    from_edges(n, (0..n).map(|start| (start, (start + 1) % n)).collect())
+}
+
+pub fn path_graph(n: usize) -> Generated {
+    from_edges(n, (1..n).map(|end| (end - 1, end)).collect())
+}
+
+fn from_edges(n: usize, edges: Vec<(usize, usize)>) -> Generated {
+    let mut graph: Generated = Graph::new();
+    for id in 0..n {
+        graph
+            .add_vertex(id, (), Vicinity::Outgoing { edges: None })
+            .expect("generated ids are unique");
+    }
+    for (start, end) in edges {
+        graph
+            .add_edge((), start, end)
+            .expect("generated edges join existing vertices");
+    }
+    graph
+}
+
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_edge_counts() {
+        let edge_count = |graph: Generated| graph.edges_iter().count();
+
+        assert_eq!(edge_count(complete_graph(5)), 20);
+        assert_eq!(edge_count(cycle_graph(5)), 5);
+        assert_eq!(edge_count(path_graph(5)), 4);
+        assert_eq!(edge_count(erdos_renyi(8, 1.0, 7)), 56);
+        assert_eq!(erdos_renyi(8, 0.5, 7), erdos_renyi(8, 0.5, 7));
+    }
+}
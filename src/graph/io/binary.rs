@@ -0,0 +1,150 @@
+use std::{cell::RefCell, path::Path, rc::Rc};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::graph::{Comparator, Graph, State, Vertex, Vicinity};
+use crate::Error;
+
+/// Flattened form encoded with `bincode`: vertices as `(id, info)` followed
+/// by edges as `(start, end, info)`. The state is not recorded, so a dump can
+/// be loaded under any state.
+type Flat<V, E, Id> = (Vec<(Id, V)>, Vec<(Id, Id, E)>);
+
+impl<V, E, Id, S, C> Graph<V, E, Id, S, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    S: State,
+    C: Comparator<Id>,
+{
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error>
+    where
+        V: Serialize,
+        E: Serialize,
+        Id: Serialize,
+    {
+        let (mut vertices, mut edges): Flat<V, E, Id> = (Vec::default(), Vec::default());
+        for (id, vertex) in self.vertices.into_vec() {
+            let vertex = vertex.borrow();
+            vertices.push((id, vertex.info.clone()));
+            let list = match &vertex.vicinity {
+                Vicinity::Outgoing { edges }
+                | Vicinity::Ingoing { edges }
+                | Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: edges,
+                } => edges,
+            };
+            edges.extend(
+                list.iter()
+                    .flatten()
+                    .filter(|edge| !edge.is_dangling())
+                    .map(|edge| (edge.get_start_id(), edge.get_end_id(), edge.info.clone())),
+            );
+        }
+        bincode::serialize(&(vertices, edges)).map_err(|_| Error::ErrorSerializing)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error>
+    where
+        V: DeserializeOwned,
+        E: DeserializeOwned,
+        Id: DeserializeOwned,
+    {
+        let (vertices, edges): Flat<V, E, Id> =
+            bincode::deserialize(bytes).map_err(|_| Error::ErrorDeserializing)?;
+
+        let mut graph: Self = Graph::new();
+        for (id, info) in vertices {
+            if graph.vertices.contains(id) {
+                return Err(Error::ErrorDeserializing);
+            }
+            let vertex = Vertex::new(id, info, S::empty_vicinity());
+            graph
+                .vertices
+                .insert(id, Rc::new(RefCell::new(vertex)))
+                .map_err(|_| Error::ErrorDeserializing)?;
+        }
+        for (start, end, info) in edges {
+            graph
+                .add_edge(info, start, end)
+                .map_err(|_| Error::ErrorDeserializing)?;
+        }
+        Ok(graph)
+    }
+
+    pub fn save_binary(&self, path: &Path) -> Result<(), Error>
+    where
+        V: Serialize,
+        E: Serialize,
+        Id: Serialize,
+    {
+        std::fs::write(path, self.to_bytes()?).map_err(|_| Error::IoError)
+    }
+
+    pub fn load_binary(path: &Path) -> Result<Self, Error>
+    where
+        V: DeserializeOwned,
+        E: DeserializeOwned,
+        Id: DeserializeOwned,
+    {
+        let bytes = std::fs::read(path).map_err(|_| Error::IoError)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{WithBoth, WithIngoing, WithOutgoing};
+
+    fn round_trip<S: State>() {
+        let mut graph: Graph<String, (u32, Option<char>), i64, S> = Graph::new();
+        for (id, info) in [(-1, "root"), (0, "leaf"), (7, "")] {
+            let vertex = Vertex::new(id, info.to_string(), S::empty_vicinity());
+            graph
+                .vertices
+                .insert(id, Rc::new(RefCell::new(vertex)))
+                .unwrap();
+        }
+        graph.add_edge((1, Some('x')), -1, 0).unwrap();
+        graph.add_edge((2, None), 0, 7).unwrap();
+        graph.add_edge((3, None), 7, 7).unwrap();
+
+        let loaded = Graph::from_bytes(&graph.to_bytes().unwrap()).unwrap();
+        assert!(loaded == graph);
+    }
+
+    #[test]
+    fn save_load_round_trip() {
+        round_trip::<WithOutgoing>();
+        round_trip::<WithIngoing>();
+        round_trip::<WithBoth>();
+
+        let mut graph = Graph::<(), u32, u32, WithOutgoing>::new();
+        for id in [1, 2] {
+            graph
+                .add_vertex(id, (), Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge(5, 1, 2).unwrap();
+        let path =
+            std::env::temp_dir().join(format!("graphrs_round_trip_{}.bin", std::process::id()));
+        graph.save_binary(&path).unwrap();
+        let loaded = Graph::load_binary(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), graph);
+    }
+
+    #[test]
+    fn corrupt_bytes_are_rejected() {
+        let truncated = Graph::<(), (), u32, WithOutgoing>::from_bytes(&[2, 0]);
+        assert!(matches!(truncated, Err(Error::ErrorDeserializing)));
+
+        let dangling: Flat<(), (), u32> = (vec![(1, ())], vec![(1, 2, ())]);
+        let bytes = bincode::serialize(&dangling).unwrap();
+        let loaded = Graph::<(), (), u32, WithOutgoing>::from_bytes(&bytes);
+        assert!(matches!(loaded, Err(Error::ErrorDeserializing)));
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt::Display;
+
+use super::escape_xml;
+use crate::graph::{Comparator, Graph, Vicinity, WithOutgoing};
+
+type DataFn<'a, T> = Option<&'a dyn Fn(&T) -> String>;
+
+impl<V, E, Id, C> Graph<V, E, Id, WithOutgoing, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    /// Structure only: vertex ids and edge endpoints, without `<data>` keys.
+    pub fn to_graphml(&self) -> String
+    where
+        Id: Display,
+    {
+        self.write_graphml(None, None)
+    }
+
+    /// Like `to_graphml`, with vertex and edge info attached as `<data>` elements.
+    pub fn to_graphml_with_data(&self) -> String
+    where
+        Id: Display,
+        V: Display,
+        E: Display,
+    {
+        self.write_graphml(
+            Some(&|info: &V| info.to_string()),
+            Some(&|info: &E| info.to_string()),
+        )
+    }
+
+    fn write_graphml(&self, vertex_data: DataFn<V>, edge_data: DataFn<E>) -> String
+    where
+        Id: Display,
+    {
+        let element = |name: &str, attributes: String, key: &str, data: Option<String>| match data {
+            Some(data) => format!(
+                "    <{name} {attributes}><data key=\"{key}\">{}</data></{name}>\n",
+                escape_xml(&data)
+            ),
+            None => format!("    <{name} {attributes}/>\n"),
+        };
+        let mut keys = String::default();
+        let mut nodes = String::default();
+        let mut edges = String::default();
+
+        if vertex_data.is_some() {
+            keys.push_str(
+                "  <key id=\"v_info\" for=\"node\" attr.name=\"info\" attr.type=\"string\"/>\n",
+            );
+        }
+        if edge_data.is_some() {
+            keys.push_str(
+                "  <key id=\"e_info\" for=\"edge\" attr.name=\"info\" attr.type=\"string\"/>\n",
+            );
+        }
+
+        for (id, vertex) in self.vertices.into_vec() {
+            let vertex = vertex.borrow();
+            nodes.push_str(&element(
+                "node",
+                format!("id=\"{}\"", escape_xml(&id.to_string())),
+                "v_info",
+                vertex_data.map(|data| data(&vertex.info)),
+            ));
+
+            if let Vicinity::Outgoing {
+                edges: Some(outgoing),
+            } = &vertex.vicinity
+            {
+                for edge in outgoing.iter().filter(|edge| !edge.is_dangling()) {
+                    edges.push_str(&element(
+                        "edge",
+                        format!(
+                            "source=\"{}\" target=\"{}\"",
+                            escape_xml(&id.to_string()),
+                            escape_xml(&edge.get_end_id().to_string())
+                        ),
+                        "e_info",
+                        edge_data.map(|data| data(&edge.info)),
+                    ));
+                }
+            }
+        }
+
+        format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+                "{}",
+                "  <graph id=\"G\" edgedefault=\"directed\">\n",
+                "{}{}",
+                "  </graph>\n",
+                "</graphml>\n"
+            ),
+            keys, nodes, edges
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(document: &roxmltree::Document, name: &str) -> usize {
+        document
+            .descendants()
+            .filter(|node| node.has_tag_name(name))
+            .count()
+    }
+
+    #[test]
+    fn graphml_is_well_formed() {
+        let mut graph = Graph::<&str, u32, &str, WithOutgoing>::default();
+        for (id, info) in [("a<1>", "<a & b>"), ("b\"2\"", "b"), ("c'3'", "c")] {
+            graph
+                .add_vertex(id, info, Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge(4, "a<1>", "b\"2\"").unwrap();
+        graph.add_edge(5, "b\"2\"", "c'3'").unwrap();
+
+        let xml = graph.to_graphml();
+        let structure = roxmltree::Document::parse(&xml).unwrap();
+        assert_eq!(count(&structure, "node"), 3);
+        assert_eq!(count(&structure, "edge"), 2);
+        assert_eq!(count(&structure, "data"), 0);
+
+        let xml = graph.to_graphml_with_data();
+        let with_data = roxmltree::Document::parse(&xml).unwrap();
+        assert_eq!(count(&with_data, "node"), 3);
+        assert_eq!(count(&with_data, "edge"), 2);
+        assert_eq!(count(&with_data, "key"), 2);
+
+        let node = with_data
+            .descendants()
+            .find(|node| node.attribute("id") == Some("a<1>"))
+            .unwrap();
+        let data = node.children().find(|child| child.has_tag_name("data"));
+        assert_eq!(data.and_then(|data| data.text()), Some("<a & b>"));
+        let edge = with_data
+            .descendants()
+            .find(|node| node.has_tag_name("edge"))
+            .unwrap();
+        assert_eq!(edge.attribute("target"), Some("b\"2\""));
+    }
+}
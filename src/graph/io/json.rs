@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use super::escape_json;
+use crate::graph::{Comparator, Graph, Vicinity, WithOutgoing};
+
+impl<V, E, Id, C> Graph<V, E, Id, WithOutgoing, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    pub fn to_json(&self) -> String
+    where
+        Id: Display,
+        V: Display,
+        E: Display,
+    {
+        let mut nodes: Vec<String> = Vec::default();
+        let mut edges: Vec<String> = Vec::default();
+
+        for (id, vertex) in self.vertices.into_vec() {
+            let vertex = vertex.borrow();
+            nodes.push(format!(
+                "{{\"id\":\"{}\",\"info\":\"{}\"}}",
+                escape_json(&id.to_string()),
+                escape_json(&vertex.info.to_string())
+            ));
+
+            if let Vicinity::Outgoing {
+                edges: Some(outgoing),
+            } = &vertex.vicinity
+            {
+                for edge in outgoing.iter().filter(|edge| !edge.is_dangling()) {
+                    edges.push(format!(
+                        "{{\"from\":\"{}\",\"to\":\"{}\",\"info\":\"{}\"}}",
+                        escape_json(&id.to_string()),
+                        escape_json(&edge.get_end_id().to_string()),
+                        escape_json(&edge.info.to_string())
+                    ));
+                }
+            }
+        }
+
+        format!(
+            "{{\"nodes\":[{}],\"edges\":[{}]}}",
+            nodes.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_escapes_strings() {
+        let mut graph = Graph::<&str, &str, u32, WithOutgoing>::default();
+        for (id, info) in [(1, "say \"hi\""), (2, "tab\there"), (3, "line\nbreak")] {
+            graph
+                .add_vertex(id, info, Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge("a\\b", 1, 2).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json()).unwrap();
+        let nodes = json["nodes"].as_array().unwrap();
+        let edges = json["edges"].as_array().unwrap();
+        assert_eq!((nodes.len(), edges.len()), (3, 1));
+        assert_eq!(nodes[0]["id"], "1");
+        assert_eq!(nodes[0]["info"], "say \"hi\"");
+        assert_eq!(nodes[1]["info"], "tab\there");
+        assert_eq!(nodes[2]["info"], "line\nbreak");
+        assert_eq!(edges[0]["from"], "1");
+        assert_eq!(edges[0]["to"], "2");
+        assert_eq!(edges[0]["info"], "a\\b");
+    }
+}
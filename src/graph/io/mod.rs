@@ -0,0 +1,37 @@
+#[cfg(feature = "bincode")]
+pub mod binary;
+pub mod graphml;
+pub mod json;
+
+fn escape_xml(value: &str) -> String {
+    value
+        .chars()
+        .fold(String::with_capacity(value.len()), |mut acc, c| {
+            match c {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '"' => acc.push_str("&quot;"),
+                '\'' => acc.push_str("&apos;"),
+                _ => acc.push(c),
+            }
+            acc
+        })
+}
+
+fn escape_json(value: &str) -> String {
+    value
+        .chars()
+        .fold(String::with_capacity(value.len()), |mut acc, c| {
+            match c {
+                '"' => acc.push_str("\\\""),
+                '\\' => acc.push_str("\\\\"),
+                '\n' => acc.push_str("\\n"),
+                '\r' => acc.push_str("\\r"),
+                '\t' => acc.push_str("\\t"),
+                c if (c as u32) < 0x20 => acc.push_str(&format!("\\u{:04x}", c as u32)),
+                _ => acc.push(c),
+            }
+            acc
+        })
+}
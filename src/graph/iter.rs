@@ -0,0 +1,294 @@
+use super::{
+    btree::BTree,
+    definitions::{Comp, Shared, Vertex},
+    Comparator, Vicinity,
+};
+use crate::Error;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+type Vertices<V, E, Id, C> = BTree<Id, Shared<Vertex<V, E, Id>>, C>;
+
+pub struct BfsIter<'a, V, E, Id, C = Comp>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    vertices: &'a Vertices<V, E, Id, C>,
+    queue: VecDeque<Id>,
+    discovered: BTreeSet<Id>,
+}
+
+impl<'a, V, E, Id, C> BfsIter<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    pub(super) fn new(vertices: &'a Vertices<V, E, Id, C>, start: Id) -> Self {
+        Self {
+            vertices,
+            queue: VecDeque::from([start]),
+            discovered: BTreeSet::default(),
+        }
+    }
+}
+
+impl<'a, V, E, Id, C> Iterator for BfsIter<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    type Item = Id;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(id) = self.queue.pop_front() {
+            if !self.discovered.insert(id) {
+                continue;
+            }
+            let Ok(vertex) = self.vertices.search(id) else {
+                continue;
+            };
+            self.queue.extend(successors(&vertex.borrow()));
+            return Some(id);
+        }
+        None
+    }
+}
+
+pub struct DfsIter<'a, V, E, Id, C = Comp>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    vertices: &'a Vertices<V, E, Id, C>,
+    stack: Vec<Id>,
+    discovered: BTreeSet<Id>,
+}
+
+impl<'a, V, E, Id, C> DfsIter<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    pub(super) fn new(vertices: &'a Vertices<V, E, Id, C>, start: Id) -> Self {
+        Self {
+            vertices,
+            stack: vec![start],
+            discovered: BTreeSet::default(),
+        }
+    }
+}
+
+impl<'a, V, E, Id, C> Iterator for DfsIter<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    type Item = Id;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(id) = self.stack.pop() {
+            if !self.discovered.insert(id) {
+                continue;
+            }
+            let Ok(vertex) = self.vertices.search(id) else {
+                continue;
+            };
+            self.stack.extend(successors(&vertex.borrow()));
+            return Some(id);
+        }
+        None
+    }
+}
+
+/// Kahn's algorithm, yielding each id once its in-degree drops to zero. If a
+/// cycle blocks the remaining vertices, a single `Error::CycleDetected` is
+/// yielded last.
+pub struct TopologicalIter<'a, V, E, Id, C = Comp>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    vertices: &'a Vertices<V, E, Id, C>,
+    in_degrees: BTreeMap<Id, usize>,
+    ready: VecDeque<Id>,
+    remaining: usize,
+}
+
+impl<'a, V, E, Id, C> TopologicalIter<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    pub(super) fn new(vertices: &'a Vertices<V, E, Id, C>) -> Self {
+        let all = vertices.into_vec();
+        let mut in_degrees: BTreeMap<Id, usize> = all.iter().map(|(id, _)| (*id, 0)).collect();
+        for (_, vertex) in &all {
+            for next in successors(&vertex.borrow()) {
+                *in_degrees.entry(next).or_default() += 1;
+            }
+        }
+        let ready = in_degrees
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&id, _)| id)
+            .collect();
+        Self {
+            vertices,
+            remaining: in_degrees.len(),
+            in_degrees,
+            ready,
+        }
+    }
+}
+
+impl<'a, V, E, Id, C> Iterator for TopologicalIter<'a, V, E, Id, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: Ord + Copy,
+    C: Comparator<Id>,
+{
+    type Item = Result<Id, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(id) = self.ready.pop_front() else {
+            if self.remaining > 0 {
+                self.remaining = 0;
+                return Some(Err(Error::CycleDetected));
+            }
+            return None;
+        };
+        self.remaining -= 1;
+        if let Ok(vertex) = self.vertices.search(id) {
+            for next in successors(&vertex.borrow()) {
+                let Some(degree) = self.in_degrees.get_mut(&next) else {
+                    continue;
+                };
+                *degree -= 1;
+                if *degree == 0 {
+                    self.ready.push_back(next);
+                }
+            }
+        }
+        Some(Ok(id))
+    }
+}
+
+fn successors<V, E, Id>(vertex: &Vertex<V, E, Id>) -> Vec<Id>
+where
+    E: Clone,
+    Id: Copy,
+{
+    let edges = match &vertex.vicinity {
+        Vicinity::Outgoing { edges }
+        | Vicinity::Both {
+            ingoing_edges: _,
+            outgoing_edges: edges,
+        } => edges,
+        Vicinity::Ingoing { edges: _ } => return vec![],
+    };
+    edges
+        .iter()
+        .flatten()
+        .filter_map(|edge| edge.end.0.upgrade())
+        .map(|end| end.borrow().id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{
+        definitions::{WithBoth, WithOutgoing},
+        Graph, Vicinity,
+    };
+    use crate::Error;
+
+    type Digraph = Graph<(), (), u32, WithOutgoing>;
+
+    fn diamond() -> Digraph {
+        let mut graph = Digraph::new();
+        for id in 1..=5 {
+            graph
+                .add_vertex(id, (), Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        for (start, end) in [(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)] {
+            graph.add_edge((), start, end).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn bfs_iter_stops_early() {
+        let graph = diamond();
+        assert_eq!(graph.bfs_iter(1).take(3).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(graph.bfs_iter(1).count(), 5);
+    }
+
+    struct Order(Vec<u32>);
+
+    impl std::ops::Add for Order {
+        type Output = Order;
+
+        fn add(mut self, rhs: Order) -> Order {
+            self.0.extend(rhs.0);
+            self
+        }
+    }
+
+    #[test]
+    fn dfs_iter_matches_depth_first_traversal() {
+        let graph = diamond();
+        let Order(expected) = graph
+            .depth_first_traversal(1, Order(vec![]), Box::new(|vertex| Order(vec![vertex.id])))
+            .unwrap();
+        assert_eq!(graph.dfs_iter(1).collect::<Vec<_>>(), expected);
+
+        let mut seen = vec![];
+        for id in graph.dfs_iter(1) {
+            if id == 4 {
+                break;
+            }
+            seen.push(id);
+        }
+        assert_eq!(
+            seen,
+            expected[..expected.iter().position(|&id| id == 4).unwrap()]
+        );
+    }
+
+    #[test]
+    fn topological_iter_yields_valid_order_or_cycle() {
+        type Bigraph = Graph<(), (), u32, WithBoth>;
+        let dag =
+            Bigraph::from_edges_strict([(3, 1, ()), (1, 2, ()), (3, 2, ()), (4, 2, ())]).unwrap();
+        let order: Vec<u32> = dag.topological_iter().map(Result::unwrap).collect();
+        assert_eq!(order.len(), 4);
+        for (start, end, _) in dag.edges_iter() {
+            let position = |id| order.iter().position(|&other| other == id).unwrap();
+            assert!(position(start) < position(end));
+        }
+
+        let cyclic = Bigraph::from_edges_strict([(1, 2, ()), (2, 3, ()), (3, 2, ())]).unwrap();
+        let mut iter = cyclic.topological_iter();
+        assert_eq!(iter.next().unwrap().unwrap(), 1);
+        assert!(matches!(iter.next(), Some(Err(Error::CycleDetected))));
+        assert!(iter.next().is_none());
+    }
+}
@@ -1,57 +1,346 @@
 use crate::Error;
-use std::{fmt::Debug, marker::PhantomData, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+    rc::Rc,
+};
 
 pub mod definitions;
 use definitions::{Comp, Edge, Shared, Vertex};
-pub use definitions::{VertexFn, VertexFnMut, Vicinity, WithBoth, WithIngoing, WithOutgoing};
+pub use definitions::{
+    style::GraphStyle,
+    weight::{Measure, Weight, WeightedPath},
+    AdjacencyMatrix, GraphDiff, State, VertexFn, VertexFnMut, Vicinity, Visitor, WithBoth,
+    WithIngoing, WithOutgoing,
+};
 
+pub mod builder;
+pub mod dag;
+pub mod entry;
+pub mod generators;
+pub mod io;
+pub mod iter;
 pub mod with_both;
 pub mod with_ingoing;
 pub mod with_outgoing;
 
 pub mod btree;
+pub use btree::key_value::Comparator;
 use btree::BTree;
+pub use builder::GraphBuilder;
+pub use dag::Dag;
+pub use entry::{EdgeEntry, VacantEdge};
 
-#[derive(Default, Debug, Clone)]
-pub struct Graph<V, E, Id, S = WithBoth>
+#[derive(Default, Debug)]
+pub struct Graph<V, E, Id, S = WithBoth, C = Comp>
 where
     V: Clone,
     E: Clone,
     Id: Ord + Copy,
+    C: Comparator<Id>,
 {
-    vertices: BTree<Id, Shared<Vertex<V, E, Id>>, Comp>,
+    vertices: BTree<Id, Shared<Vertex<V, E, Id>>, C>,
+    attributes: BTreeMap<Id, BTreeMap<String, String>>,
     state: PhantomData<S>,
 }
 
-impl<V, E, Id, S> Graph<V, E, Id, S>
+impl<V, E, Id, S, C> Graph<V, E, Id, S, C>
 where
     V: Clone,
     E: Clone,
     Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
 {
+    pub fn new() -> Self {
+        Self {
+            vertices: BTree::new(),
+            attributes: BTreeMap::new(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn builder() -> GraphBuilder<V, E, Id, S, C> {
+        GraphBuilder::new()
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn index_stats(&self) -> (usize, usize) {
+        (self.vertices.height(), self.vertices.node_count())
+    }
+
+    pub fn get_vertex_info(&self, id: Id) -> Result<V, Error> {
+        Ok(self.vertices.search(id)?.borrow().info.clone())
+    }
+
+    /// Owned copy of the vertex. Its edges are still `Observer`s pointing into
+    /// this graph, so they dangle once the graph is dropped.
+    pub fn vertex_snapshot(&self, id: Id) -> Result<Vertex<V, E, Id>, Error> {
+        Ok(self.vertices.search(id)?.borrow().clone())
+    }
+
+    pub fn with_vertex<R>(
+        &self,
+        id: Id,
+        f: impl FnOnce(&Vertex<V, E, Id>) -> R,
+    ) -> Result<R, Error> {
+        let vertex = self.vertices.search(id)?.borrow();
+        Ok(f(&vertex))
+    }
+
+    pub fn with_vertex_mut<R>(
+        &mut self,
+        id: Id,
+        f: impl FnOnce(&mut Vertex<V, E, Id>) -> R,
+    ) -> Result<R, Error> {
+        let mut vertex = self.vertices.search(id)?.borrow_mut();
+        Ok(f(&mut vertex))
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.attributes.clear();
+    }
+
+    pub fn set_attr(&mut self, id: Id, key: &str, value: &str) -> Result<(), Error> {
+        if !self.vertices.contains(id) {
+            return Err(Error::KeyWasNotFound);
+        }
+        self.attributes
+            .entry(id)
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    pub fn get_attr(&self, id: Id, key: &str) -> Option<&str> {
+        self.attributes.get(&id)?.get(key).map(String::as_str)
+    }
+
+    /// Copies the graph under new ids. Returns `Error::VertexAlreadyExists` if
+    /// `f` maps two ids to the same new id. The copy is ordered by the default
+    /// comparator; use `relabel_with` to pick another one.
+    pub fn relabel<NewId>(&self, f: impl Fn(Id) -> NewId) -> Result<Graph<V, E, NewId, S>, Error>
+    where
+        NewId: PartialOrd + Ord + PartialEq + Eq + Copy,
+    {
+        self.relabel_with::<NewId, Comp>(f)
+    }
+
+    /// Like `relabel`, ordering the copy by `NewC`.
+    pub fn relabel_with<NewId, NewC>(
+        &self,
+        f: impl Fn(Id) -> NewId,
+    ) -> Result<Graph<V, E, NewId, S, NewC>, Error>
+    where
+        NewId: PartialOrd + Ord + PartialEq + Eq + Copy,
+        NewC: Comparator<NewId>,
+    {
+        let old_vertices = self.vertices.into_vec();
+        let mut relabeled: Graph<V, E, NewId, S, NewC> = Graph::new();
+
+        for (id, vertex) in &old_vertices {
+            let vertex = vertex.borrow();
+            let vicinity = match &vertex.vicinity {
+                Vicinity::Outgoing { edges: _ } => Vicinity::Outgoing { edges: None },
+                Vicinity::Ingoing { edges: _ } => Vicinity::Ingoing { edges: None },
+                Vicinity::Both { .. } => Vicinity::Both {
+                    ingoing_edges: None,
+                    outgoing_edges: None,
+                },
+            };
+            let new_id = f(*id);
+            let copy = Vertex::new(new_id, vertex.info.clone(), vicinity);
+            relabeled
+                .vertices
+                .insert(new_id, Rc::new(RefCell::new(copy)))
+                .map_err(|_| Error::VertexAlreadyExists)?;
+            if let Some(attributes) = self.attributes.get(id) {
+                relabeled.attributes.insert(new_id, attributes.clone());
+            }
+        }
+
+        for (_, vertex) in &old_vertices {
+            let vertex = vertex.borrow();
+            let edges = match &vertex.vicinity {
+                Vicinity::Outgoing { edges }
+                | Vicinity::Ingoing { edges }
+                | Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: edges,
+                } => edges,
+            };
+            for edge in edges.iter().flatten().filter(|edge| !edge.is_dangling()) {
+                relabeled.add_edge(
+                    edge.info.clone(),
+                    f(edge.get_start_id()),
+                    f(edge.get_end_id()),
+                )?;
+            }
+        }
+        Ok(relabeled)
+    }
+
+    /// Reports edges whose start or end vertex no longer exists.
+    pub fn find_dangling_edges(&self) -> Vec<(Id, Id)> {
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .flat_map(|(_, vertex)| {
+                vertex
+                    .borrow()
+                    .edges()
+                    .into_iter()
+                    .filter(|edge| edge.is_dangling())
+                    .map(|edge| edge.endpoint_ids())
+                    .collect::<Vec<(Id, Id)>>()
+            })
+            .collect()
+    }
+
+    /// Drops every dangling edge and returns how many were removed.
+    pub fn compact(&mut self) -> usize {
+        let prune = |edges: &mut Option<Vec<Edge<V, E, Id>>>| -> usize {
+            let Some(list) = edges else {
+                return 0;
+            };
+            let before = list.len();
+            list.retain(|edge| !edge.is_dangling());
+            let removed = before - list.len();
+            if list.is_empty() {
+                *edges = None;
+            }
+            removed
+        };
+
+        let mut removed = 0;
+        for (_, vertex) in self.vertices.into_vec() {
+            removed += match &mut vertex.borrow_mut().vicinity {
+                Vicinity::Outgoing { edges } | Vicinity::Ingoing { edges } => prune(edges),
+                Vicinity::Both {
+                    ingoing_edges,
+                    outgoing_edges,
+                } => prune(ingoing_edges) + prune(outgoing_edges),
+            };
+        }
+        removed
+    }
+
+    pub fn diff(&self, other: &Graph<V, E, Id, S, C>) -> GraphDiff<Id>
+    where
+        Id: Hash,
+    {
+        let (lhs_vertices, rhs_vertices) = (self.vertex_ids(), other.vertex_ids());
+        let (lhs_edges, rhs_edges) = (self.edge_ids(), other.edge_ids());
+
+        GraphDiff {
+            added_vertices: rhs_vertices.difference(&lhs_vertices).copied().collect(),
+            removed_vertices: lhs_vertices.difference(&rhs_vertices).copied().collect(),
+            added_edges: rhs_edges.difference(&lhs_edges).copied().collect(),
+            removed_edges: lhs_edges.difference(&rhs_edges).copied().collect(),
+        }
+    }
+
+    /// Compares vertex ids and edge endpoints only, ignoring vertex and edge
+    /// info as well as parallel edge counts.
+    pub fn same_structure<OtherV, OtherE>(&self, other: &Graph<OtherV, OtherE, Id, S, C>) -> bool
+    where
+        OtherV: Clone,
+        OtherE: Clone,
+        Id: Hash,
+    {
+        self.vertex_ids() == other.vertex_ids() && self.edge_ids() == other.edge_ids()
+    }
+
+    fn matrix_index(&self) -> (Vec<Id>, BTreeMap<Id, usize>) {
+        let ids: Vec<Id> = self
+            .vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        let index = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        (ids, index)
+    }
+
+    fn vertex_ids(&self) -> HashSet<Id>
+    where
+        Id: Hash,
+    {
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn edge_ids(&self) -> HashSet<(Id, Id)>
+    where
+        Id: Hash,
+    {
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .flat_map(|(_, vertex)| {
+                vertex
+                    .borrow()
+                    .edges()
+                    .into_iter()
+                    .filter(|edge| !edge.is_dangling())
+                    .map(|edge| (edge.get_start_id(), edge.get_end_id()))
+                    .collect::<Vec<(Id, Id)>>()
+            })
+            .collect()
+    }
+
     pub fn add_edge(&mut self, info: E, start: Id, end: Id) -> Result<(), Error> {
         if !self.vertices.contains(start) || !self.vertices.contains(end) {
             return Err(Error::KeyWasNotFound);
         }
 
         let (start, end) = (self.vertices.search(start)?, self.vertices.search(end)?);
+        let edge = Edge::new(info, Rc::clone(start), Rc::clone(end));
+        if Rc::ptr_eq(start, end) {
+            // A self-loop has one endpoint, which can only be borrowed mutably once.
+            return match &mut start.borrow_mut().vicinity {
+                Vicinity::Outgoing { edges } | Vicinity::Ingoing { edges } => {
+                    edges.get_or_insert_with(Vec::new).push(edge);
+                    Ok(())
+                }
+                Vicinity::Both {
+                    ingoing_edges,
+                    outgoing_edges,
+                } => {
+                    outgoing_edges
+                        .get_or_insert_with(Vec::new)
+                        .push(edge.clone());
+                    ingoing_edges.get_or_insert_with(Vec::new).push(edge);
+                    Ok(())
+                }
+            };
+        }
         let (mut start_borrowed, mut end_borrowed) = (start.borrow_mut(), end.borrow_mut());
 
         match (&mut start_borrowed.vicinity, &mut end_borrowed.vicinity) {
             (Vicinity::Outgoing { edges }, Vicinity::Outgoing { edges: _ }) => {
                 match edges {
-                    Some(edges) => edges.push(Edge::new(info, Rc::clone(start), Rc::clone(end))),
+                    Some(edges) => edges.push(edge),
                     None => {
-                        *edges = Some(vec![Edge::new(info, Rc::clone(start), Rc::clone(end))]);
+                        *edges = Some(vec![edge]);
                     }
                 };
                 Ok(())
             }
             (Vicinity::Ingoing { edges: _ }, Vicinity::Ingoing { edges }) => {
                 match edges {
-                    Some(edges) => edges.push(Edge::new(info, Rc::clone(start), Rc::clone(end))),
+                    Some(edges) => edges.push(edge),
                     None => {
-                        *edges = Some(vec![Edge::new(info, Rc::clone(start), Rc::clone(end))]);
+                        *edges = Some(vec![edge]);
                     }
                 };
                 Ok(())
@@ -67,21 +356,15 @@ where
                 },
             ) => {
                 match outgoing {
-                    Some(edges) => {
-                        edges.push(Edge::new(info.clone(), Rc::clone(start), Rc::clone(end)))
-                    }
+                    Some(edges) => edges.push(edge.clone()),
                     None => {
-                        *outgoing = Some(vec![Edge::new(
-                            info.clone(),
-                            Rc::clone(start),
-                            Rc::clone(end),
-                        )]);
+                        *outgoing = Some(vec![edge.clone()]);
                     }
                 };
                 match ingoing {
-                    Some(edges) => edges.push(Edge::new(info, Rc::clone(start), Rc::clone(end))),
+                    Some(edges) => edges.push(edge),
                     None => {
-                        *ingoing = Some(vec![Edge::new(info, Rc::clone(start), Rc::clone(end))]);
+                        *ingoing = Some(vec![edge]);
                     }
                 };
 
@@ -91,3 +374,426 @@ where
         }
     }
 }
+
+impl<V, E, Id, S> Graph<V, E, Id, S>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+{
+    pub fn with_comparator<C: Comparator<Id>>() -> Graph<V, E, Id, S, C> {
+        Graph::new()
+    }
+}
+
+impl<V, E, Id, S, C> PartialEq for Graph<V, E, Id, S, C>
+where
+    V: Clone + PartialEq,
+    E: Clone + PartialEq,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = (self.vertices.into_vec(), other.vertices.into_vec());
+        if lhs.len() != rhs.len() {
+            return false;
+        }
+
+        let edges_of = |vertex: &Vertex<V, E, Id>| -> Vec<(Id, Id, E)> {
+            vertex
+                .edges()
+                .into_iter()
+                .filter(|edge| !edge.is_dangling())
+                .map(|edge| (edge.get_start_id(), edge.get_end_id(), edge.info.clone()))
+                .collect()
+        };
+
+        lhs.iter().zip(&rhs).all(|((lhs_id, lhs), (rhs_id, rhs))| {
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+            if lhs_id != rhs_id || lhs.info != rhs.info {
+                return false;
+            }
+
+            let mut rhs_edges = edges_of(&rhs);
+            for edge in edges_of(&lhs) {
+                match rhs_edges.iter().position(|rhs_edge| *rhs_edge == edge) {
+                    Some(index) => {
+                        rhs_edges.swap_remove(index);
+                    }
+                    None => return false,
+                }
+            }
+            rhs_edges.is_empty()
+        })
+    }
+}
+
+impl<V, E, Id, S, C> Eq for Graph<V, E, Id, S, C>
+where
+    V: Clone + Eq,
+    E: Clone + Eq,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+}
+
+/// Consistent with `PartialEq`: vertices are hashed in id order and edges as
+/// an order-independent multiset.
+impl<V, E, Id, S, C> Hash for Graph<V, E, Id, S, C>
+where
+    V: Clone + Hash,
+    E: Clone + Hash,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy + Hash,
+    C: Comparator<Id>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let vertices = self.vertices.into_vec();
+        let mut edge_hashes: Vec<u64> = Vec::default();
+        vertices.len().hash(state);
+        for (id, vertex) in &vertices {
+            let vertex = vertex.borrow();
+            id.hash(state);
+            vertex.info.hash(state);
+            for edge in vertex
+                .edges()
+                .into_iter()
+                .filter(|edge| !edge.is_dangling())
+            {
+                let mut hasher = DefaultHasher::new();
+                (edge.get_start_id(), edge.get_end_id(), &edge.info).hash(&mut hasher);
+                edge_hashes.push(hasher.finish());
+            }
+        }
+        edge_hashes.sort_unstable();
+        edge_hashes.hash(state);
+    }
+}
+
+impl<V, E, Id, S, C> Clone for Graph<V, E, Id, S, C>
+where
+    V: Clone,
+    E: Clone,
+    Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
+{
+    fn clone(&self) -> Self {
+        let old_vertices = self.vertices.into_vec();
+        let mut vertices: BTree<Id, Shared<Vertex<V, E, Id>>, C> = BTree::new();
+
+        for (id, vertex) in &old_vertices {
+            let vertex = vertex.borrow();
+            let vicinity = match &vertex.vicinity {
+                Vicinity::Outgoing { edges: _ } => Vicinity::Outgoing { edges: None },
+                Vicinity::Ingoing { edges: _ } => Vicinity::Ingoing { edges: None },
+                Vicinity::Both { .. } => Vicinity::Both {
+                    ingoing_edges: None,
+                    outgoing_edges: None,
+                },
+            };
+            let copy = Vertex::new(*id, vertex.info.clone(), vicinity);
+            vertices
+                .insert(*id, Rc::new(RefCell::new(copy)))
+                .expect("vertex ids are unique");
+        }
+
+        let rewire = |edges: &Option<Vec<Edge<V, E, Id>>>| -> Option<Vec<Edge<V, E, Id>>> {
+            edges.as_ref().map(|edges| {
+                edges
+                    .iter()
+                    .filter(|edge| !edge.is_dangling())
+                    .filter_map(|edge| {
+                        let start = vertices.search(edge.get_start_id()).ok()?;
+                        let end = vertices.search(edge.get_end_id()).ok()?;
+                        Some(Edge::new(
+                            edge.info.clone(),
+                            Rc::clone(start),
+                            Rc::clone(end),
+                        ))
+                    })
+                    .collect()
+            })
+        };
+
+        for (id, vertex) in &old_vertices {
+            let vicinity = match &vertex.borrow().vicinity {
+                Vicinity::Outgoing { edges } => Vicinity::Outgoing {
+                    edges: rewire(edges),
+                },
+                Vicinity::Ingoing { edges } => Vicinity::Ingoing {
+                    edges: rewire(edges),
+                },
+                Vicinity::Both {
+                    ingoing_edges,
+                    outgoing_edges,
+                } => Vicinity::Both {
+                    ingoing_edges: rewire(ingoing_edges),
+                    outgoing_edges: rewire(outgoing_edges),
+                },
+            };
+            if let Ok(copy) = vertices.search(*id) {
+                copy.borrow_mut().vicinity = vicinity;
+            }
+        }
+
+        Self {
+            vertices,
+            attributes: self.attributes.clone(),
+            state: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    struct Reverse;
+
+    impl Comparator<usize> for Reverse {
+        fn compare(lhs: &usize, rhs: &usize) -> Ordering {
+            rhs.cmp(lhs)
+        }
+    }
+
+    #[test]
+    fn clear_resets_a_populated_graph() {
+        let both = || Vicinity::Both {
+            ingoing_edges: None,
+            outgoing_edges: None,
+        };
+        let mut graph = Graph::<(), (), u32, WithBoth>::default();
+        graph.add_vertex(1, (), both()).unwrap();
+        graph.add_vertex(2, (), both()).unwrap();
+        graph.add_edge((), 1, 2).unwrap();
+        graph.set_attr(1, "color", "red").unwrap();
+
+        graph.clear();
+        assert_eq!(graph.vertex_count(), 0);
+        assert!(!graph.vertices.contains(1));
+        assert_eq!(graph.get_attr(1, "color"), None);
+
+        assert!(graph.add_vertex(1, (), both()).is_ok());
+        assert_eq!(graph.vertex_count(), 1);
+    }
+
+    fn two_way(edges: &[(u32, u32)]) -> Graph<(), u32, u32, WithBoth> {
+        let mut graph: Graph<(), u32, u32, WithBoth> = Graph::new();
+        for &(start, end) in edges {
+            graph.get_or_insert_vertex(start, || ()).unwrap();
+            graph.get_or_insert_vertex(end, || ()).unwrap();
+            graph.add_edge(start + end, start, end).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn equality_ignores_insertion_order() {
+        let lhs = two_way(&[(1, 2), (2, 3), (3, 1)]);
+        let rhs = two_way(&[(3, 1), (1, 2), (2, 3)]);
+        assert_eq!(lhs, rhs);
+        assert_ne!(lhs, two_way(&[(1, 2), (2, 3)]));
+    }
+
+    #[test]
+    fn clone_is_deep() {
+        let original = two_way(&[(1, 2)]);
+        let mut copy = original.clone();
+        if let Vicinity::Both { outgoing_edges, .. } =
+            &mut copy.vertices.search(1).unwrap().borrow_mut().vicinity
+        {
+            outgoing_edges
+                .iter_mut()
+                .flatten()
+                .for_each(|edge| edge.info = 0);
+        }
+        copy.add_edge(9, 2, 1).unwrap();
+
+        assert_eq!(original.edges_iter().collect::<Vec<_>>(), vec![(1, 2, 3)]);
+        assert_eq!(
+            copy.edges_iter().collect::<Vec<_>>(),
+            vec![(1, 2, 0), (2, 1, 9)]
+        );
+
+        // The copy's edges point at the copy's vertices, not the original's.
+        drop(original);
+        assert_eq!(copy.edges_iter().count(), 2);
+        assert!(copy.find_dangling_edges().is_empty());
+    }
+
+    #[test]
+    fn attributes_are_stored_per_vertex() {
+        let mut graph = two_way(&[(1, 2)]);
+        graph.set_attr(1, "score", "3").unwrap();
+        graph.set_attr(1, "score", "4").unwrap();
+        graph.set_attr(2, "visited", "yes").unwrap();
+
+        assert_eq!(graph.get_attr(1, "score"), Some("4"));
+        assert_eq!(graph.get_attr(2, "visited"), Some("yes"));
+        assert_eq!(graph.get_attr(2, "score"), None);
+        assert!(matches!(
+            graph.set_attr(3, "score", "1"),
+            Err(Error::KeyWasNotFound)
+        ));
+    }
+
+    #[test]
+    fn with_vertex_reads_edge_count() {
+        let graph = two_way(&[(1, 2), (1, 3), (3, 1)]);
+        let edge_count = |id| graph.with_vertex(id, |vertex| vertex.edges().len());
+        assert_eq!(edge_count(1).unwrap(), 3);
+        assert_eq!(edge_count(2).unwrap(), 1);
+        assert!(matches!(
+            graph.with_vertex(4, |vertex| vertex.id),
+            Err(Error::KeyWasNotFound)
+        ));
+    }
+
+    #[test]
+    fn with_vertex_mut_persists_info() {
+        let mut graph = Graph::<&str, (), u32, WithBoth>::new();
+        let vicinity = Vicinity::Both {
+            ingoing_edges: None,
+            outgoing_edges: None,
+        };
+        graph.add_vertex(1, "draft", vicinity).unwrap();
+        graph
+            .with_vertex_mut(1, |vertex| vertex.info = "final")
+            .unwrap();
+
+        assert_eq!(graph.with_vertex(1, |vertex| vertex.info).unwrap(), "final");
+        assert!(graph.with_vertex_mut(2, |_| ()).is_err());
+    }
+
+    #[test]
+    fn diff_reports_added_and_removed_items() {
+        let before = two_way(&[(1, 2), (2, 3)]);
+        let after = two_way(&[(1, 2), (2, 4)]);
+        let diff = before.diff(&after);
+
+        assert_eq!(diff.added_vertices, HashSet::from([4]));
+        assert_eq!(diff.removed_vertices, HashSet::from([3]));
+        assert_eq!(diff.added_edges, HashSet::from([(2, 4)]));
+        assert_eq!(diff.removed_edges, HashSet::from([(2, 3)]));
+        assert_eq!(before.diff(&before), GraphDiff::default());
+    }
+
+    #[test]
+    fn custom_comparator_orders_vertices() {
+        let mut graph = Graph::<(), (), usize, WithOutgoing>::with_comparator::<Reverse>();
+        for id in [2, 5, 1, 4, 3] {
+            graph
+                .add_vertex(id, (), Vicinity::Outgoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge((), 5, 1).unwrap();
+
+        let ids: Vec<usize> = graph
+            .vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec![5, 4, 3, 2, 1]);
+        assert!(graph.has_edge(5, 1));
+    }
+
+    #[test]
+    fn relabel_to_sequential_ids() {
+        let names = ["alpha", "beta", "gamma"];
+        let graph = Graph::<u32, char, &str, WithOutgoing>::builder()
+            .vertex("alpha", 1)
+            .vertex("beta", 2)
+            .vertex("gamma", 3)
+            .edge('x', "alpha", "beta")
+            .edge('y', "gamma", "alpha")
+            .build()
+            .unwrap();
+        let index = |id: &str| names.iter().position(|name| *name == id).unwrap();
+
+        let relabeled = graph.relabel(index).unwrap();
+        assert_eq!(relabeled.get_vertex_info(2).unwrap(), 3);
+        assert_eq!(relabeled.get_edge_info(0, 1).unwrap(), 'x');
+        assert_eq!(relabeled.get_edge_info(2, 0).unwrap(), 'y');
+
+        let reversed = graph.relabel_with::<usize, Reverse>(index).unwrap();
+        let ids: Vec<usize> = reversed
+            .vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, vec![2, 1, 0]);
+
+        assert!(matches!(
+            graph.relabel(|_| 0),
+            Err(Error::VertexAlreadyExists)
+        ));
+    }
+
+    #[test]
+    fn removed_vertex_leaves_dangling_edges() {
+        let mut graph = two_way(&[(1, 2), (2, 3), (3, 1)]);
+        assert!(graph.find_dangling_edges().is_empty());
+
+        drop(graph.vertices.remove(2).unwrap());
+        let mut dangling = graph.find_dangling_edges();
+        dangling.sort();
+        assert_eq!(dangling, vec![(1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn compact_removes_dangling_edges() {
+        let mut graph = two_way(&[(1, 2), (2, 3), (3, 1)]);
+        drop(graph.vertices.remove(2).unwrap());
+
+        assert_eq!(graph.compact(), 2);
+        assert!(graph.find_dangling_edges().is_empty());
+        assert_eq!(graph.compact(), 0);
+        let visited = graph.depth_first_traversal(3, 0, Box::new(|_| 1)).unwrap();
+        assert_eq!(visited, 2);
+        assert_eq!(graph.edges_iter().collect::<Vec<_>>(), vec![(3, 1, 4)]);
+    }
+
+    #[test]
+    fn vertex_snapshot_is_detached() {
+        let mut graph = two_way(&[(1, 2), (1, 3), (3, 1)]);
+        let snapshot = graph.vertex_snapshot(1).unwrap();
+        graph.add_edge(5, 2, 1).unwrap();
+
+        assert_eq!(snapshot.id, 1);
+        assert_eq!(snapshot.edges().len(), 3);
+        assert_eq!(graph.vertex_snapshot(1).unwrap().edges().len(), 4);
+        assert!(graph.vertex_snapshot(4).is_err());
+    }
+
+    #[test]
+    fn hash_ignores_insertion_order() {
+        let hash = |graph: &Graph<(), u32, u32, WithBoth>| {
+            let mut hasher = DefaultHasher::new();
+            graph.hash(&mut hasher);
+            hasher.finish()
+        };
+        let lhs = two_way(&[(1, 2), (2, 3), (3, 1)]);
+        let rhs = two_way(&[(3, 1), (2, 3), (1, 2)]);
+
+        assert_eq!(hash(&lhs), hash(&rhs));
+        assert_ne!(hash(&lhs), hash(&two_way(&[(1, 2), (2, 3)])));
+    }
+
+    #[test]
+    fn same_structure_ignores_weights() {
+        let weighted = two_way(&[(1, 2), (2, 3)]);
+        let reweighted = Graph::<&str, u32, u32, WithBoth>::builder()
+            .vertex(1, "a")
+            .vertex(2, "b")
+            .vertex(3, "c")
+            .edge(100, 2, 3)
+            .edge(200, 1, 2)
+            .build()
+            .unwrap();
+
+        assert!(weighted.same_structure(&reweighted));
+        assert!(!weighted.same_structure(&two_way(&[(1, 2), (3, 2)])));
+    }
+}
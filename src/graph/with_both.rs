@@ -1,28 +1,37 @@
+use super::definitions::style::quote_dot;
+use super::with_outgoing::topological_dfs;
 pub use super::{
     definitions::{
         path::{Path, Paths, ResultUnit, Unit},
-        Vertex, VertexFn, VertexFnMut, Vicinity, WithBoth,
+        style::GraphStyle,
+        weight::Weight,
+        AdjacencyMatrix, Edge, Shared, Vertex, VertexFn, VertexFnMut, Vicinity, Visitor, WithBoth,
     },
-    Graph,
+    iter::{BfsIter, DfsIter, TopologicalIter},
+    Comparator, Graph,
 };
 use crate::Error;
-use dot_writer::{Attributes, Color, DotWriter, Shape, Style};
+use dot_writer::{Attributes, DotWriter, Shape, Style};
 use std::{
     cell::RefCell,
-    collections::VecDeque,
-    fmt::Display,
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt::{Debug, Display},
     fs::File,
+    hash::Hash,
     io::Write,
     process::{Command, Stdio},
+    rc::Rc,
     str,
 };
 
 #[allow(dead_code)]
-impl<V, E, Id> Graph<V, E, Id, WithBoth>
+impl<V, E, Id, C> Graph<V, E, Id, WithBoth, C>
 where
     V: Clone,
     E: Clone,
     Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
 {
     pub fn add_vertex(
         &mut self,
@@ -46,6 +55,256 @@ where
         Ok(())
     }
 
+    pub fn get_or_insert_vertex(
+        &mut self,
+        id: Id,
+        info: impl FnOnce() -> V,
+    ) -> Result<&Shared<Vertex<V, E, Id>>, Error> {
+        if !self.vertices.contains(id) {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            self.add_vertex(id, info(), vicinity)?;
+        }
+        self.vertices.search(id)
+    }
+
+    /// Builds a graph from `(start, end, info)` triples, creating vertices with
+    /// default info on first sight. A repeated `(start, end)` pair is rejected
+    /// with `Error::EdgeAlreadyExists` instead of forming a multigraph.
+    pub fn from_edges_strict(edges: impl IntoIterator<Item = (Id, Id, E)>) -> Result<Self, Error>
+    where
+        V: Default,
+    {
+        let mut graph = Self::new();
+        for (start, end, info) in edges {
+            graph.get_or_insert_vertex(start, V::default)?;
+            graph.get_or_insert_vertex(end, V::default)?;
+            if graph.has_edge(start, end) {
+                return Err(Error::EdgeAlreadyExists);
+            }
+            graph.add_edge(info, start, end)?;
+        }
+        Ok(graph)
+    }
+
+    /// Returns the id of the first vertex whose vicinity does not match the
+    /// graph's state, paired with `Error::MismatchedVicinity`.
+    pub fn validate(&self) -> Result<(), (Id, Error)> {
+        for (id, vertex) in self.vertices.into_vec() {
+            if !matches!(
+                &vertex.borrow().vicinity,
+                Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: _,
+                }
+            ) {
+                return Err((id, Error::MismatchedVicinity));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn edges_iter(&self) -> impl Iterator<Item = (Id, Id, E)> {
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .flat_map(|(id, vertex)| match &vertex.borrow().vicinity {
+                Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: Some(edges),
+                } => edges
+                    .iter()
+                    .filter(|edge| !edge.is_dangling())
+                    .map(|edge| (id, edge.get_end_id(), edge.info.clone()))
+                    .collect(),
+                _ => vec![],
+            })
+    }
+
+    pub fn has_edge(&self, start: Id, end: Id) -> bool {
+        let Ok(vertex) = self.vertices.search(start) else {
+            return false;
+        };
+        let vertex = vertex.borrow();
+        match &vertex.vicinity {
+            Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges: Some(edges),
+            } => edges
+                .iter()
+                .filter(|edge| !edge.is_dangling())
+                .any(|edge| edge.get_end_id() == end),
+            _ => false,
+        }
+    }
+
+    pub fn is_adjacent(&self, a: Id, b: Id) -> bool {
+        self.has_edge(a, b) || self.has_edge(b, a)
+    }
+
+    pub fn incident_edges(&self, id: Id) -> Result<Vec<(Id, Id, E)>, Error> {
+        let vertex = self.vertices.search(id)?.borrow();
+        let Vicinity::Both {
+            ingoing_edges,
+            outgoing_edges,
+        } = &vertex.vicinity
+        else {
+            return Err(Error::MismatchedVicinity);
+        };
+        Ok(ingoing_edges
+            .iter()
+            .chain(outgoing_edges.iter())
+            .flatten()
+            .filter(|edge| !edge.is_dangling())
+            .map(|edge| (edge.get_start_id(), edge.get_end_id(), edge.info.clone()))
+            .collect())
+    }
+
+    /// Reverses one `start -> end` edge. With parallel edges the ingoing copy
+    /// is matched by `info` as well, so both lists drop the same edge.
+    pub fn flip_edge(&mut self, start: Id, end: Id) -> Result<(), Error>
+    where
+        E: PartialEq,
+    {
+        if !self.has_edge(start, end) {
+            return Err(Error::EdgeWasNotFound);
+        }
+
+        let take =
+            |vertex: &Shared<Vertex<V, E, Id>>, outgoing: bool, info: Option<&E>| -> Option<E> {
+                let mut vertex = vertex.borrow_mut();
+                let Vicinity::Both {
+                    ingoing_edges,
+                    outgoing_edges,
+                } = &mut vertex.vicinity
+                else {
+                    return None;
+                };
+                let edges = if outgoing {
+                    outgoing_edges.as_mut()?
+                } else {
+                    ingoing_edges.as_mut()?
+                };
+                let position = edges.iter().position(|edge| {
+                    !edge.is_dangling()
+                        && edge.endpoint_ids() == (start, end)
+                        && info.is_none_or(|info| edge.info == *info)
+                })?;
+                Some(edges.remove(position).info)
+            };
+
+        let info = take(self.vertices.search(start)?, true, None).ok_or(Error::EdgeWasNotFound)?;
+        take(self.vertices.search(end)?, false, Some(&info));
+        self.add_edge(info, end, start)
+    }
+
+    pub fn clear_edges(&mut self, id: Id) -> Result<usize, Error> {
+        let target = Rc::clone(self.vertices.search(id)?);
+        let removed = match &mut target.borrow_mut().vicinity {
+            Vicinity::Both {
+                ingoing_edges,
+                outgoing_edges,
+            } => {
+                let outgoing = outgoing_edges.take().unwrap_or_default();
+                let ingoing = ingoing_edges.take().unwrap_or_default();
+                outgoing.len()
+                    + ingoing
+                        .iter()
+                        .filter(|edge| !std::ptr::eq(edge.start.0.as_ptr(), Rc::as_ptr(&target)))
+                        .count()
+            }
+            _ => return Err(Error::MismatchedVicinity),
+        };
+
+        let detach = |edges: &mut Option<Vec<Edge<V, E, Id>>>, outgoing: bool| -> usize {
+            let Some(list) = edges else {
+                return 0;
+            };
+            let before = list.len();
+            list.retain(|edge| {
+                let neighbor = if outgoing { &edge.end } else { &edge.start };
+                !std::ptr::eq(neighbor.0.as_ptr(), Rc::as_ptr(&target))
+            });
+            let detached = before - list.len();
+            if list.is_empty() {
+                *edges = None;
+            }
+            detached
+        };
+
+        for (_, vertex) in self.vertices.into_vec() {
+            if let Vicinity::Both {
+                ingoing_edges,
+                outgoing_edges,
+            } = &mut vertex.borrow_mut().vicinity
+            {
+                // Each mirrored copy belongs to an edge already counted above.
+                detach(ingoing_edges, false);
+                detach(outgoing_edges, true);
+            }
+        }
+        Ok(removed)
+    }
+
+    pub fn contract_edge(&mut self, a: Id, b: Id) -> Result<(), Error> {
+        if !self.vertices.contains(a) || !self.vertices.contains(b) {
+            return Err(Error::KeyWasNotFound);
+        }
+
+        let incident: Vec<(Id, Id, E)> = self
+            .edges_iter()
+            .filter(|(start, end, _)| *start == b || *end == b)
+            .collect();
+        if !incident
+            .iter()
+            .any(|(start, end, _)| (*start, *end) == (a, b) || (*start, *end) == (b, a))
+        {
+            return Err(Error::EdgeWasNotFound);
+        }
+
+        self.clear_edges(b)?;
+        for (start, end, info) in incident {
+            let start = if start == b { a } else { start };
+            let end = if end == b { a } else { end };
+            if start != end {
+                self.add_edge(info, start, end)?;
+            }
+        }
+        self.vertices.remove(b)?;
+        self.attributes.remove(&b);
+        Ok(())
+    }
+
+    pub fn weighted_out_degree(&self, id: Id) -> Result<E::Output, Error>
+    where
+        E: Weight,
+    {
+        let vertex = self.vertices.search(id)?.borrow();
+        match &vertex.vicinity {
+            Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges,
+            } => Ok(Self::total_weight(outgoing_edges)),
+            _ => Err(Error::MismatchedVicinity),
+        }
+    }
+
+    pub fn weighted_in_degree(&self, id: Id) -> Result<E::Output, Error>
+    where
+        E: Weight,
+    {
+        let vertex = self.vertices.search(id)?.borrow();
+        match &vertex.vicinity {
+            Vicinity::Both {
+                ingoing_edges,
+                outgoing_edges: _,
+            } => Ok(Self::total_weight(ingoing_edges)),
+            _ => Err(Error::MismatchedVicinity),
+        }
+    }
+
     pub fn depth_first_traversal<R>(
         &self,
         initial_id: Id,
@@ -73,8 +332,10 @@ where
                 } = unsafe { &(*vertex).vicinity }
                 {
                     for edge in edges {
-                        let binding = edge.end.0.upgrade().ok_or(Error::NullPointer);
-                        let id = binding?.borrow().id;
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
+                        let id = binding.borrow().id;
                         stack.push_back(id);
                     }
                 }
@@ -112,8 +373,10 @@ where
                 } = unsafe { &(*vertex).vicinity }
                 {
                     for edge in edges {
-                        let binding = edge.end.0.upgrade().ok_or(Error::NullPointer);
-                        let id = binding?.borrow().id;
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
+                        let id = binding.borrow().id;
                         queue.push_back(id);
                     }
                 }
@@ -151,8 +414,10 @@ where
                 } = unsafe { &(*vertex).vicinity }
                 {
                     for edge in edges {
-                        let binding = edge.end.0.upgrade().ok_or(Error::NullPointer);
-                        let id = binding?.borrow().id;
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
+                        let id = binding.borrow().id;
                         stack.push_back(id);
                     }
                 }
@@ -190,8 +455,10 @@ where
                 } = unsafe { &(*vertex).vicinity }
                 {
                     for edge in edges {
-                        let binding = edge.end.0.upgrade().ok_or(Error::NullPointer);
-                        let id = binding?.borrow().id;
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
+                        let id = binding.borrow().id;
                         queue.push_back(id);
                     }
                 }
@@ -202,44 +469,152 @@ where
         Ok(acc)
     }
 
-    pub fn all_paths_from(&self, id: Id) -> Result<Paths<V, E, Id>, Error> {
-        let create_paths = |v: &Vertex<V, E, Id>| -> Paths<V, E, Id> {
-            match &v.vicinity {
-                Vicinity::Outgoing { edges } => {
-                    if let Some(edges) = edges {
-                        let mut paths: Vec<Path<V, E, Id>> = vec![];
-                        for edge in edges {
-                            let path = Path(vec![edge.clone()]);
-                            paths.push(path);
-                        }
-                        Paths(paths)
-                    } else {
-                        Paths(vec![])
-                    }
+    pub fn fold_dfs<R>(
+        &self,
+        initial_id: Id,
+        init: R,
+        mut f: impl FnMut(R, &Vertex<V, E, Id>) -> R,
+    ) -> Result<R, Error> {
+        let mut acc = init;
+        let mut discovered: Vec<Id> = Vec::default();
+        let mut stack: VecDeque<Id> = VecDeque::from([initial_id]);
+
+        while let Some(id) = stack.pop_back() {
+            if discovered.contains(&id) {
+                continue;
+            }
+            discovered.push(id);
+
+            let vertex = self.vertices.search(id)?.borrow();
+            acc = f(acc, &vertex);
+
+            if let Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges: Some(edges),
+            } = &vertex.vicinity
+            {
+                for edge in edges {
+                    let Some(binding) = edge.end.0.upgrade() else {
+                        continue;
+                    };
+                    stack.push_back(binding.borrow().id);
                 }
-                Vicinity::Both {
-                    ingoing_edges: _,
-                    outgoing_edges: edges,
-                } => {
-                    if let Some(edges) = edges {
-                        let mut paths: Vec<Path<V, E, Id>> = vec![];
-                        for edge in edges {
-                            let path = Path(vec![edge.clone()]);
-                            paths.push(path);
-                        }
-                        Paths(paths)
-                    } else {
-                        Paths(vec![])
-                    }
+            }
+        }
+        Ok(acc)
+    }
+
+    pub fn fold_bfs<R>(
+        &self,
+        initial_id: Id,
+        init: R,
+        mut f: impl FnMut(R, &Vertex<V, E, Id>) -> R,
+    ) -> Result<R, Error> {
+        let mut acc = init;
+        let mut discovered: Vec<Id> = Vec::default();
+        let mut queue: VecDeque<Id> = VecDeque::from([initial_id]);
+
+        while let Some(id) = queue.pop_front() {
+            if discovered.contains(&id) {
+                continue;
+            }
+            discovered.push(id);
+
+            let vertex = self.vertices.search(id)?.borrow();
+            acc = f(acc, &vertex);
+
+            if let Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges: Some(edges),
+            } = &vertex.vicinity
+            {
+                for edge in edges {
+                    let Some(binding) = edge.end.0.upgrade() else {
+                        continue;
+                    };
+                    queue.push_back(binding.borrow().id);
                 }
-                _ => panic!("Cannot do if I only know about ingoing edges"),
             }
-        };
+        }
+        Ok(acc)
+    }
+
+    pub fn bfs_iter(&self, start: Id) -> BfsIter<'_, V, E, Id, C> {
+        BfsIter::new(&self.vertices, start)
+    }
 
-        self.breadth_first_traversal(id, Paths(Vec::default()), Box::new(create_paths))
+    pub fn topological_iter(&self) -> TopologicalIter<'_, V, E, Id, C> {
+        TopologicalIter::new(&self.vertices)
+    }
+
+    pub fn dfs_iter(&self, start: Id) -> DfsIter<'_, V, E, Id, C> {
+        DfsIter::new(&self.vertices, start)
+    }
+
+    pub fn traverse_dfs(
+        &self,
+        start: Id,
+        visitor: &mut impl Visitor<V, E, Id>,
+    ) -> Result<(), Error> {
+        self.fold_dfs(start, (), |(), vertex| visitor.visit(vertex))
+    }
+
+    pub fn traverse_bfs(
+        &self,
+        start: Id,
+        visitor: &mut impl Visitor<V, E, Id>,
+    ) -> Result<(), Error> {
+        self.fold_bfs(start, (), |(), vertex| visitor.visit(vertex))
+    }
+
+    pub fn all_paths_from(&self, id: Id) -> Result<Paths<V, E, Id>, Error> {
+        let mut paths: Vec<Path<V, E, Id>> = Vec::default();
+        let mut visited: Vec<Id> = vec![id];
+        let mut current: Vec<Edge<V, E, Id>> = Vec::default();
+        self.collect_paths(id, None, &mut visited, &mut current, &mut paths)?;
+
+        Ok(Paths(paths))
     }
 
     pub fn dump_to_file(&self, initial_id: Id, file: &RefCell<std::fs::File>) -> ResultUnit
+    where
+        Id: Display,
+    {
+        self.dump_to_file_styled(initial_id, file, &GraphStyle::default())
+    }
+
+    pub fn dump_to_file_styled(
+        &self,
+        initial_id: Id,
+        file: &RefCell<std::fs::File>,
+        style: &GraphStyle,
+    ) -> ResultUnit
+    where
+        Id: Display,
+    {
+        self.dump_dot(initial_id, file, style, &[])
+    }
+
+    pub fn dump_clustered(
+        &self,
+        initial_id: Id,
+        file: &RefCell<std::fs::File>,
+        style: &GraphStyle,
+        clusters: &[(String, Vec<Id>)],
+    ) -> ResultUnit
+    where
+        Id: Display,
+    {
+        self.dump_dot(initial_id, file, style, clusters)
+    }
+
+    fn dump_dot(
+        &self,
+        initial_id: Id,
+        file: &RefCell<std::fs::File>,
+        style: &GraphStyle,
+        clusters: &[(String, Vec<Id>)],
+    ) -> ResultUnit
     where
         Id: Display,
     {
@@ -248,22 +623,38 @@ where
         let writer = unsafe { &mut (*writer) };
         let digraph = RefCell::new(writer.digraph());
 
-        digraph.borrow_mut().set_font("FiraCode Mone Nerd Font");
+        digraph.borrow_mut().set_font(&style.font);
         digraph.borrow_mut().set_shape(Shape::Mrecord);
-        digraph.borrow_mut().set_background_color(Color::Gray20);
+        digraph
+            .borrow_mut()
+            .set_background_color(style.background_color);
         digraph.borrow_mut().set_style(Style::Filled);
+        digraph.borrow_mut().set_rank_direction(style.rankdir);
         {
             let mut bind = digraph.borrow_mut();
             let mut node_attr = bind.node_attributes();
             node_attr.set_style(Style::Filled);
-            node_attr.set_shape(Shape::Circle);
-            node_attr.set_font("FiraCode Mono Nerd Font");
-            node_attr.set_color(Color::LightGrey);
+            node_attr.set_shape(style.node_shape);
+            node_attr.set_font(&style.font);
+            node_attr.set_color(style.node_color);
         }
         {
             let mut bind = digraph.borrow_mut();
             let mut edge_attr = bind.edge_attributes();
-            edge_attr.set_color(Color::White);
+            edge_attr.set_color(style.edge_color);
+        }
+        for (label, ids) in clusters {
+            let mut bind = digraph.borrow_mut();
+            let mut cluster = bind.cluster();
+            cluster.set("label", &quote_dot(label), false);
+            for id in ids.iter().filter(|id| self.vertices.contains(**id)) {
+                cluster.node_named(quote_dot(&id.to_string()));
+            }
+        }
+        for (id, _) in self.vertices.into_vec() {
+            if !clusters.iter().any(|(_, ids)| ids.contains(&id)) {
+                digraph.borrow_mut().node_named(quote_dot(&id.to_string()));
+            }
         }
 
         let digraph = digraph.as_ptr();
@@ -272,19 +663,29 @@ where
             match &v.vicinity {
                 Vicinity::Outgoing { edges: Some(edges) } => {
                     for edge in edges {
-                        let binding = edge.end.0.upgrade().unwrap();
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
                         let edge_id = binding.borrow().id;
                         let digraph = unsafe { &mut (*digraph) };
-                        digraph.edge(v.id.to_string(), edge_id.to_string());
+                        digraph.edge(
+                            quote_dot(&v.id.to_string()),
+                            quote_dot(&edge_id.to_string()),
+                        );
                     }
                     Unit(()).into()
                 }
                 Vicinity::Ingoing { edges: Some(edges) } => {
                     for edge in edges {
-                        let binding = edge.end.0.upgrade().unwrap();
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
                         let edge_id = binding.borrow().id;
                         let digraph = unsafe { &mut (*digraph) };
-                        digraph.edge(v.id.to_string(), edge_id.to_string());
+                        digraph.edge(
+                            quote_dot(&v.id.to_string()),
+                            quote_dot(&edge_id.to_string()),
+                        );
                     }
                     Unit(()).into()
                 }
@@ -293,10 +694,15 @@ where
                     outgoing_edges: _,
                 } => {
                     for edge in ingoing_edges {
-                        let binding = edge.end.0.upgrade().unwrap();
+                        let Some(binding) = edge.end.0.upgrade() else {
+                            continue;
+                        };
                         let edge_id = binding.borrow().id;
                         let digraph = unsafe { &mut (*digraph) };
-                        digraph.edge(v.id.to_string(), edge_id.to_string());
+                        digraph.edge(
+                            quote_dot(&v.id.to_string()),
+                            quote_dot(&edge_id.to_string()),
+                        );
                     }
 
                     Unit(()).into()
@@ -341,22 +747,1179 @@ where
         Ok(())
     }
 
-    pub fn all_paths_between(&self, start: Id, end: Id) -> Option<Vec<Path<V, E, Id>>>
+    /// Rows and columns follow the returned id order. Parallel edges keep the
+    /// smallest weight.
+    pub fn to_adjacency_matrix(&self) -> AdjacencyMatrix<Id, Option<E::Output>>
     where
-        Id: Default + std::ops::Add<Output = Id>,
+        E: Weight,
     {
-        let paths_between = self
-            .all_paths_from(start)
-            .ok()?
-            .0
-            .iter()
-            .filter_map(|path| -> Option<Path<V, E, Id>> { path.subpath_between(start, end).ok() })
-            .collect::<Vec<Path<V, E, Id>>>();
+        let (ids, index) = self.matrix_index();
+        let mut matrix: Vec<Vec<Option<E::Output>>> = vec![vec![None; ids.len()]; ids.len()];
+        for (start, end, info) in self.edges_iter() {
+            let cell = &mut matrix[index[&start]][index[&end]];
+            let weight = info.weight();
+            if !cell.is_some_and(|known| known <= weight) {
+                *cell = Some(weight);
+            }
+        }
+        (ids, matrix)
+    }
+
+    pub fn to_boolean_matrix(&self) -> AdjacencyMatrix<Id, bool> {
+        let (ids, index) = self.matrix_index();
+        let mut matrix: Vec<Vec<bool>> = vec![vec![false; ids.len()]; ids.len()];
+        for (start, end, _) in self.edges_iter() {
+            matrix[index[&start]][index[&end]] = true;
+        }
+        (ids, matrix)
+    }
+
+    pub fn stream_dot<W: Write>(&self, out: &mut W) -> Result<(), Error>
+    where
+        Id: Display,
+    {
+        writeln!(out, "digraph {{").map_err(|_| Error::IoError)?;
+        for (id, _) in self.vertices.into_vec() {
+            writeln!(out, "  {};", quote_dot(&id.to_string())).map_err(|_| Error::IoError)?;
+        }
+        for (start, end, _) in self.edges_iter() {
+            writeln!(
+                out,
+                "  {} -> {};",
+                quote_dot(&start.to_string()),
+                quote_dot(&end.to_string())
+            )
+            .map_err(|_| Error::IoError)?;
+        }
+        writeln!(out, "}}").map_err(|_| Error::IoError)
+    }
 
-        if paths_between.is_empty() {
+    pub fn all_paths_between(&self, start: Id, end: Id) -> Option<Vec<Path<V, E, Id>>> {
+        let mut paths: Vec<Path<V, E, Id>> = Vec::default();
+        let mut visited: Vec<Id> = vec![start];
+        let mut current: Vec<Edge<V, E, Id>> = Vec::default();
+        self.collect_paths(start, Some(end), &mut visited, &mut current, &mut paths)
+            .ok()?;
+
+        if paths.is_empty() {
             None
         } else {
-            Some(paths_between)
+            Some(paths)
+        }
+    }
+
+    fn collect_paths(
+        &self,
+        id: Id,
+        end: Option<Id>,
+        visited: &mut Vec<Id>,
+        current: &mut Vec<Edge<V, E, Id>>,
+        paths: &mut Vec<Path<V, E, Id>>,
+    ) -> Result<(), Error> {
+        let vertex = self.vertices.search(id)?.borrow();
+        let mut extended = false;
+
+        let edges = match &vertex.vicinity {
+            Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges,
+            } => outgoing_edges.iter().flatten(),
+            _ => return Err(Error::MismatchedVicinity),
+        };
+        for edge in edges.filter(|edge| !edge.is_dangling()) {
+            let next = edge.get_end_id();
+            if visited.contains(&next) {
+                continue;
+            }
+
+            extended = true;
+            current.push(edge.clone());
+            if Some(next) == end {
+                paths.push(Path(current.clone()));
+            } else {
+                visited.push(next);
+                self.collect_paths(next, end, visited, current, paths)?;
+                visited.pop();
+            }
+            current.pop();
+        }
+
+        if end.is_none() && !extended && !current.is_empty() {
+            paths.push(Path(current.clone()));
+        }
+        Ok(())
+    }
+
+    /// Vertices that are ancestors of both `a` and `b`. As with `ancestors`,
+    /// neither `a` nor `b` counts as its own ancestor.
+    pub fn common_ancestors(&self, a: Id, b: Id) -> Result<HashSet<Id>, Error>
+    where
+        Id: Hash,
+    {
+        let (lhs, rhs) = (self.ancestors(a)?, self.ancestors(b)?);
+        Ok(lhs.intersection(&rhs).copied().collect())
+    }
+
+    /// Common ancestors of `a` and `b` with no descendant that is also a
+    /// common ancestor, in ascending order. A DAG may have several.
+    pub fn lowest_common_ancestors(&self, a: Id, b: Id) -> Result<Vec<Id>, Error>
+    where
+        Id: Hash,
+    {
+        let common = self.common_ancestors(a, b)?;
+        let mut ancestors: HashMap<Id, HashSet<Id>> = HashMap::default();
+        for &candidate in &common {
+            ancestors.insert(candidate, self.ancestors(candidate)?);
+        }
+
+        let mut lowest: Vec<Id> = common
+            .iter()
+            .copied()
+            .filter(|candidate| {
+                !ancestors
+                    .iter()
+                    .any(|(other, above)| other != candidate && above.contains(candidate))
+            })
+            .collect();
+        lowest.sort();
+        Ok(lowest)
+    }
+
+    pub fn ancestors(&self, id: Id) -> Result<HashSet<Id>, Error>
+    where
+        Id: Hash,
+    {
+        if !self.vertices.contains(id) {
+            return Err(Error::KeyWasNotFound);
+        }
+
+        let mut ancestors: HashSet<Id> = HashSet::default();
+        let mut queue: VecDeque<Id> = VecDeque::from([id]);
+
+        while let Some(current) = queue.pop_front() {
+            let vertex = self.vertices.search(current)?.borrow();
+            if let Vicinity::Both {
+                ingoing_edges: Some(edges),
+                outgoing_edges: _,
+            } = &vertex.vicinity
+            {
+                for edge in edges {
+                    let Some(binding) = edge.start.0.upgrade() else {
+                        continue;
+                    };
+                    let start_id = binding.borrow().id;
+                    if start_id != id && ancestors.insert(start_id) {
+                        queue.push_back(start_id);
+                    }
+                }
+            }
+        }
+        Ok(ancestors)
+    }
+
+    pub fn connected_components(&self) -> Vec<Vec<Id>> {
+        let mut discovered: BTreeSet<Id> = BTreeSet::default();
+        let mut components: Vec<Vec<Id>> = Vec::default();
+
+        for (id, _) in self.vertices.into_vec() {
+            if !discovered.insert(id) {
+                continue;
+            }
+
+            let mut component: Vec<Id> = Vec::default();
+            let mut stack: Vec<Id> = vec![id];
+            while let Some(current) = stack.pop() {
+                component.push(current);
+                for neighbor in self.neighbor_ids(current) {
+                    if discovered.insert(neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Induced subgraph of the component containing `id`, treating edges as
+    /// undirected.
+    pub fn component_subgraph(&self, id: Id) -> Result<Graph<V, E, Id, WithBoth, C>, Error> {
+        if !self.vertices.contains(id) {
+            return Err(Error::KeyWasNotFound);
+        }
+
+        let mut members: BTreeSet<Id> = BTreeSet::from([id]);
+        let mut stack: Vec<Id> = vec![id];
+        while let Some(current) = stack.pop() {
+            for neighbor in self.neighbor_ids(current) {
+                if members.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        let mut subgraph: Graph<V, E, Id, WithBoth, C> = Graph::new();
+        for &member in &members {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            subgraph.add_vertex(member, self.get_vertex_info(member)?, vicinity)?;
+        }
+        for (start, end, info) in self.edges_iter() {
+            if members.contains(&start) {
+                subgraph.add_edge(info, start, end)?;
+            }
+        }
+        Ok(subgraph)
+    }
+
+    /// Largest weighted distance from `id`, treating edges as undirected.
+    /// `None` when some vertex cannot be reached from `id`.
+    pub fn weighted_eccentricity(&self, id: Id) -> Result<Option<E::Output>, Error>
+    where
+        E: Weight,
+    {
+        if !self.vertices.contains(id) {
+            return Err(Error::KeyWasNotFound);
+        }
+        let distances = self.undirected_distances(id);
+        if distances.len() < self.vertex_count() {
+            return Ok(None);
+        }
+        Ok(distances
+            .into_values()
+            .max_by(|lhs, rhs| lhs.partial_cmp(rhs).unwrap_or(Ordering::Equal)))
+    }
+
+    /// Largest weighted eccentricity over all vertices. `None` for empty or
+    /// disconnected graphs.
+    pub fn weighted_diameter(&self) -> Option<E::Output>
+    where
+        E: Weight,
+    {
+        let mut diameter: Option<E::Output> = None;
+        for (id, _) in self.vertices.into_vec() {
+            let eccentricity = self.weighted_eccentricity(id).ok()??;
+            if !diameter.is_some_and(|known| known >= eccentricity) {
+                diameter = Some(eccentricity);
+            }
+        }
+        diameter
+    }
+
+    /// Components of the underlying undirected graph. Since `connected_components`
+    /// already follows both ingoing and outgoing edges, the two coincide here.
+    pub fn weakly_connected_components(&self) -> Vec<Vec<Id>> {
+        self.connected_components()
+    }
+
+    /// Edges are treated as undirected. The empty graph is considered connected.
+    pub fn is_connected(&self) -> bool {
+        let vertices = self.vertices.into_vec();
+        let Some((first, _)) = vertices.first() else {
+            return true;
+        };
+
+        let mut discovered: BTreeSet<Id> = BTreeSet::from([*first]);
+        let mut queue: VecDeque<Id> = VecDeque::from([*first]);
+        while let Some(current) = queue.pop_front() {
+            for neighbor in self.neighbor_ids(current) {
+                if discovered.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        discovered.len() == vertices.len()
+    }
+
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let ids: Vec<Id> = self
+            .vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        let mut parent: Vec<usize> = (0..ids.len()).collect();
+        let mut size: Vec<usize> = vec![1; ids.len()];
+
+        let find = |parent: &mut Vec<usize>, mut index: usize| -> usize {
+            while parent[index] != index {
+                parent[index] = parent[parent[index]];
+                index = parent[index];
+            }
+            index
+        };
+
+        for (start, end, _) in self.edges_iter() {
+            // `ids` follow the graph's comparator, not `Id`'s natural order.
+            let position = |target: &Id| ids.binary_search_by(|id| C::compare(id, target));
+            let (Ok(start), Ok(end)) = (position(&start), position(&end)) else {
+                continue;
+            };
+            let (mut lhs, mut rhs) = (find(&mut parent, start), find(&mut parent, end));
+            if lhs == rhs {
+                continue;
+            }
+            if size[lhs] < size[rhs] {
+                std::mem::swap(&mut lhs, &mut rhs);
+            }
+            parent[rhs] = lhs;
+            size[lhs] += size[rhs];
+        }
+
+        let mut sizes: Vec<usize> = (0..ids.len())
+            .filter(|&index| parent[index] == index)
+            .map(|index| size[index])
+            .collect();
+        sizes.sort_by(|lhs, rhs| rhs.cmp(lhs));
+        sizes
+    }
+
+    pub fn is_forest(&self) -> bool {
+        let components = self.connected_components().len();
+        self.edges_iter().count() + components == self.vertex_count()
+    }
+
+    pub fn is_tree(&self) -> bool {
+        self.connected_components().len() == 1 && self.is_forest()
+    }
+
+    /// Edges are treated as undirected, so a path exists when the edges form one
+    /// component with zero or two odd-degree vertices. The returned sequence lists
+    /// the visited vertices, one more than the number of edges.
+    pub fn eulerian_path(&self) -> Option<Vec<Id>> {
+        let edges: Vec<(Id, Id)> = self
+            .edges_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect();
+
+        let mut adjacency: BTreeMap<Id, Vec<(Id, usize)>> = BTreeMap::default();
+        for (id, _) in self.vertices.into_vec() {
+            adjacency.insert(id, Vec::default());
+        }
+        for (index, &(start, end)) in edges.iter().enumerate() {
+            adjacency.get_mut(&start)?.push((end, index));
+            adjacency.get_mut(&end)?.push((start, index));
+        }
+
+        let odd: Vec<Id> = adjacency
+            .iter()
+            .filter(|(_, incident)| incident.len() % 2 == 1)
+            .map(|(id, _)| *id)
+            .collect();
+        if !matches!(odd.len(), 0 | 2) {
+            return None;
+        }
+        let start = match odd.first() {
+            Some(id) => *id,
+            None => adjacency
+                .iter()
+                .find(|(_, incident)| !incident.is_empty())
+                .or(adjacency.iter().next())
+                .map(|(id, _)| *id)?,
+        };
+
+        let mut used: Vec<bool> = vec![false; edges.len()];
+        let mut stack: Vec<Id> = vec![start];
+        let mut path: Vec<Id> = Vec::default();
+        while let Some(&current) = stack.last() {
+            let incident = adjacency.get_mut(&current)?;
+            while incident.last().is_some_and(|(_, index)| used[*index]) {
+                incident.pop();
+            }
+            match incident.pop() {
+                Some((next, index)) => {
+                    used[index] = true;
+                    stack.push(next);
+                }
+                None => {
+                    path.push(current);
+                    stack.pop();
+                }
+            }
+        }
+
+        if path.len() != edges.len() + 1 {
+            return None;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Backtracking search for a path through every vertex that follows edge
+    /// direction. The search is exponential and only meant for small graphs.
+    pub fn hamiltonian_path(&self) -> Option<Vec<Id>> {
+        let ids: Vec<Id> = self
+            .vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        if ids.is_empty() {
+            return Some(vec![]);
+        }
+
+        for start in ids.iter().copied() {
+            let mut path: Vec<Id> = vec![start];
+            let mut visited: BTreeSet<Id> = BTreeSet::from([start]);
+            if self.extend_hamiltonian(&mut path, &mut visited, ids.len()) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Pairs count as adjacent when an edge joins them in either direction. Each
+    /// missing pair gets one edge from the smaller id to the larger one.
+    pub fn complement(&self, info: impl Fn(Id, Id) -> E) -> Graph<V, E, Id, WithBoth, C> {
+        let vertices = self.vertices.into_vec();
+        let mut complement: Graph<V, E, Id, WithBoth, C> = Graph::new();
+        for (id, vertex) in &vertices {
+            complement
+                .add_vertex(
+                    *id,
+                    vertex.borrow().info.clone(),
+                    Vicinity::Both {
+                        ingoing_edges: None,
+                        outgoing_edges: None,
+                    },
+                )
+                .expect("ids of a graph are unique");
+        }
+
+        for (index, (start, _)) in vertices.iter().enumerate() {
+            let neighbors = self.neighbor_ids(*start);
+            for (end, _) in vertices.iter().skip(index + 1) {
+                if !neighbors.contains(end) {
+                    complement
+                        .add_edge(info(*start, *end), *start, *end)
+                        .expect("both endpoints were added above");
+                }
+            }
+        }
+        complement
+    }
+
+    pub fn degree_histogram(&self) -> BTreeMap<usize, usize> {
+        Self::histogram(
+            self.degrees()
+                .into_iter()
+                .map(|(ingoing, outgoing)| ingoing + outgoing),
+        )
+    }
+
+    pub fn in_degree_histogram(&self) -> BTreeMap<usize, usize> {
+        Self::histogram(self.degrees().into_iter().map(|(ingoing, _)| ingoing))
+    }
+
+    pub fn out_degree_histogram(&self) -> BTreeMap<usize, usize> {
+        Self::histogram(self.degrees().into_iter().map(|(_, outgoing)| outgoing))
+    }
+
+    /// Assigns each vertex the length, in edges, of the longest path reaching
+    /// it from any source.
+    pub fn schedule_levels(&self) -> Result<HashMap<Id, usize>, Error>
+    where
+        Id: Hash,
+    {
+        let mut in_degrees: BTreeMap<Id, usize> = self
+            .vertices
+            .into_vec()
+            .into_iter()
+            .map(|(id, _)| (id, 0))
+            .collect();
+        let mut successors: BTreeMap<Id, Vec<Id>> = BTreeMap::default();
+        for (start, end, _) in self.edges_iter() {
+            *in_degrees.entry(end).or_default() += 1;
+            successors.entry(start).or_default().push(end);
+        }
+        let mut ready: VecDeque<Id> = in_degrees
+            .iter()
+            .filter(|(_, &degree)| degree == 0)
+            .map(|(&id, _)| id)
+            .collect();
+        let mut levels: HashMap<Id, usize> = ready.iter().map(|&id| (id, 0)).collect();
+        while let Some(id) = ready.pop_front() {
+            let level = levels[&id];
+            for &next in successors.get(&id).into_iter().flatten() {
+                let next_level = levels.entry(next).or_default();
+                *next_level = (*next_level).max(level + 1);
+                let Some(degree) = in_degrees.get_mut(&next) else {
+                    continue;
+                };
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        if in_degrees.values().any(|&degree| degree > 0) {
+            return Err(Error::WithMessage("cycle"));
+        }
+        Ok(levels)
+    }
+
+    pub fn topological_sort(&self, start_id: Id) -> Result<VecDeque<Id>, Error>
+    where
+        Id: Debug,
+        V: Debug,
+        E: Debug,
+    {
+        topological_dfs(&self.vertices, start_id, &|vertex| match &vertex.vicinity {
+            Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges: edges,
+            } => edges
+                .iter()
+                .flatten()
+                .map(|edge| edge.end.0.upgrade().ok_or(Error::NullPointer))
+                .collect(),
+            _ => Err(Error::MismatchedVicinity),
+        })
+    }
+
+    pub fn sources(&self) -> Vec<Id> {
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .filter_map(|(id, vertex)| match &vertex.borrow().vicinity {
+                Vicinity::Both {
+                    ingoing_edges: Some(edges),
+                    outgoing_edges: _,
+                } if edges.iter().any(|edge| !edge.is_dangling()) => None,
+                _ => Some(id),
+            })
+            .collect()
+    }
+
+    pub fn sinks(&self) -> Vec<Id> {
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .filter_map(|(id, vertex)| match &vertex.borrow().vicinity {
+                Vicinity::Both {
+                    ingoing_edges: _,
+                    outgoing_edges: Some(edges),
+                } if edges.iter().any(|edge| !edge.is_dangling()) => None,
+                _ => Some(id),
+            })
+            .collect()
+    }
+
+    fn extend_hamiltonian(
+        &self,
+        path: &mut Vec<Id>,
+        visited: &mut BTreeSet<Id>,
+        total: usize,
+    ) -> bool {
+        if path.len() == total {
+            return true;
+        }
+
+        let Some(&last) = path.last() else {
+            return false;
+        };
+        for neighbor in self.successor_ids(last) {
+            if !visited.insert(neighbor) {
+                continue;
+            }
+            path.push(neighbor);
+            if self.extend_hamiltonian(path, visited, total) {
+                return true;
+            }
+            path.pop();
+            visited.remove(&neighbor);
+        }
+        false
+    }
+
+    fn successor_ids(&self, id: Id) -> Vec<Id> {
+        let Ok(vertex) = self.vertices.search(id) else {
+            return vec![];
+        };
+        match &vertex.borrow().vicinity {
+            Vicinity::Both {
+                ingoing_edges: _,
+                outgoing_edges: Some(edges),
+            } => edges
+                .iter()
+                .filter_map(|edge| edge.end.0.upgrade())
+                .map(|end| end.borrow().id)
+                .collect(),
+            _ => vec![],
         }
     }
+
+    fn undirected_distances(&self, start: Id) -> BTreeMap<Id, E::Output>
+    where
+        E: Weight,
+    {
+        let mut distances: BTreeMap<Id, E::Output> =
+            BTreeMap::from([(start, E::Output::default())]);
+        let mut settled: BTreeSet<Id> = BTreeSet::default();
+
+        while let Some((current, distance)) = distances
+            .iter()
+            .filter(|(id, _)| !settled.contains(*id))
+            .min_by(|(_, lhs), (_, rhs)| lhs.partial_cmp(rhs).unwrap_or(Ordering::Equal))
+            .map(|(id, distance)| (*id, *distance))
+        {
+            settled.insert(current);
+            let Ok(vertex) = self.vertices.search(current) else {
+                continue;
+            };
+            let vertex = vertex.borrow();
+            let Vicinity::Both {
+                ingoing_edges,
+                outgoing_edges,
+            } = &vertex.vicinity
+            else {
+                continue;
+            };
+            let ingoing = ingoing_edges
+                .iter()
+                .flatten()
+                .filter(|edge| !edge.is_dangling())
+                .map(|edge| (edge.get_start_id(), edge.info.weight()));
+            let outgoing = outgoing_edges
+                .iter()
+                .flatten()
+                .filter(|edge| !edge.is_dangling())
+                .map(|edge| (edge.get_end_id(), edge.info.weight()));
+            for (next, weight) in ingoing.chain(outgoing) {
+                if settled.contains(&next) {
+                    continue;
+                }
+                let candidate = distance + weight;
+                if !distances
+                    .get(&next)
+                    .is_some_and(|known| *known <= candidate)
+                {
+                    distances.insert(next, candidate);
+                }
+            }
+        }
+        distances
+    }
+
+    fn total_weight(edges: &Option<Vec<Edge<V, E, Id>>>) -> E::Output
+    where
+        E: Weight,
+    {
+        edges
+            .iter()
+            .flatten()
+            .filter(|edge| !edge.is_dangling())
+            .fold(E::Output::default(), |total, edge| {
+                total + edge.info.weight()
+            })
+    }
+
+    fn degrees(&self) -> Vec<(usize, usize)> {
+        let count = |edges: &Option<Vec<Edge<V, E, Id>>>| {
+            edges
+                .iter()
+                .flatten()
+                .filter(|edge| !edge.is_dangling())
+                .count()
+        };
+
+        self.vertices
+            .into_vec()
+            .into_iter()
+            .map(|(_, vertex)| match &vertex.borrow().vicinity {
+                Vicinity::Both {
+                    ingoing_edges,
+                    outgoing_edges,
+                } => (count(ingoing_edges), count(outgoing_edges)),
+                _ => (0, 0),
+            })
+            .collect()
+    }
+
+    fn histogram(degrees: impl Iterator<Item = usize>) -> BTreeMap<usize, usize> {
+        let mut histogram: BTreeMap<usize, usize> = BTreeMap::default();
+        for degree in degrees {
+            *histogram.entry(degree).or_default() += 1;
+        }
+        histogram
+    }
+
+    fn neighbor_ids(&self, id: Id) -> Vec<Id> {
+        let Ok(vertex) = self.vertices.search(id) else {
+            return vec![];
+        };
+        match &vertex.borrow().vicinity {
+            Vicinity::Both {
+                ingoing_edges,
+                outgoing_edges,
+            } => {
+                let ingoing = ingoing_edges
+                    .iter()
+                    .flatten()
+                    .filter_map(|edge| edge.start.0.upgrade());
+                let outgoing = outgoing_edges
+                    .iter()
+                    .flatten()
+                    .filter_map(|edge| edge.end.0.upgrade());
+                ingoing.chain(outgoing).map(|end| end.borrow().id).collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Bigraph = Graph<(), (), u32, WithBoth>;
+
+    fn bigraph(edges: &[(u32, u32)]) -> Bigraph {
+        let mut graph = Bigraph::default();
+        for &(start, end) in edges {
+            for id in [start, end] {
+                if !graph.vertices.contains(id) {
+                    let vicinity = Vicinity::Both {
+                        ingoing_edges: None,
+                        outgoing_edges: None,
+                    };
+                    graph.add_vertex(id, (), vicinity).unwrap();
+                }
+            }
+            graph.add_edge((), start, end).unwrap();
+        }
+        graph
+    }
+
+    fn weighted_bigraph(edges: &[(u32, u32, u32)]) -> Graph<(), u32, u32, WithBoth> {
+        let mut graph = Graph::<(), u32, u32, WithBoth>::new();
+        for &(start, end, weight) in edges {
+            for id in [start, end] {
+                if !graph.vertices.contains(id) {
+                    let vicinity = Vicinity::Both {
+                        ingoing_edges: None,
+                        outgoing_edges: None,
+                    };
+                    graph.add_vertex(id, (), vicinity).unwrap();
+                }
+            }
+            graph.add_edge(weight, start, end).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn edges_iter_yields_each_edge_once() {
+        let graph = bigraph(&[(1, 2), (2, 3), (3, 1)]);
+        let edges: Vec<(u32, u32)> = graph
+            .edges_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect();
+        assert_eq!(edges, vec![(1, 2), (2, 3), (3, 1)]);
+    }
+
+    #[test]
+    fn ancestors_follow_ingoing_edges() {
+        let graph = bigraph(&[(1, 2), (2, 3), (4, 2)]);
+        assert_eq!(graph.ancestors(3).unwrap(), HashSet::from([1, 2, 4]));
+        assert_eq!(graph.ancestors(1).unwrap(), HashSet::new());
+        assert!(matches!(graph.ancestors(9), Err(Error::KeyWasNotFound)));
+    }
+
+    #[test]
+    fn tree_and_forest_detection() {
+        let tree = bigraph(&[(1, 2), (1, 3), (3, 4)]);
+        assert!(tree.is_tree() && tree.is_forest());
+
+        let forest = bigraph(&[(1, 2), (3, 4)]);
+        assert!(forest.is_forest() && !forest.is_tree());
+
+        let cyclic = bigraph(&[(1, 2), (2, 3), (3, 1)]);
+        assert!(!cyclic.is_forest() && !cyclic.is_tree());
+    }
+
+    #[test]
+    fn sources_and_sinks_of_a_dag() {
+        let graph = bigraph(&[(1, 3), (2, 3), (3, 4)]);
+        assert_eq!(graph.sources(), vec![1, 2]);
+        assert_eq!(graph.sinks(), vec![4]);
+    }
+
+    #[test]
+    fn clear_edges_detaches_vertex() {
+        let mut graph = bigraph(&[(1, 2), (2, 3), (3, 2), (1, 3)]);
+        assert_eq!(graph.clear_edges(2).unwrap(), 3);
+
+        assert!(graph.vertices.contains(2));
+        let edges: Vec<(u32, u32)> = graph
+            .edges_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect();
+        assert_eq!(edges, vec![(1, 3)]);
+        assert!(!graph.neighbor_ids(1).contains(&2));
+        assert!(!graph.neighbor_ids(3).contains(&2));
+    }
+
+    #[test]
+    fn contract_edge_merges_neighbor_sets() {
+        let mut graph = bigraph(&[(1, 2), (2, 3), (4, 2), (1, 5)]);
+        graph.contract_edge(1, 2).unwrap();
+
+        let neighbors: BTreeSet<u32> = graph.neighbor_ids(1).into_iter().collect();
+        assert_eq!(neighbors, BTreeSet::from([3, 4, 5]));
+        assert!(!graph.vertices.contains(2));
+        assert!(matches!(
+            graph.contract_edge(3, 5),
+            Err(Error::EdgeWasNotFound)
+        ));
+    }
+
+    #[test]
+    fn connectivity() {
+        let connected = bigraph(&[(1, 2), (3, 2), (3, 4)]);
+        assert!(connected.is_connected());
+
+        let split = bigraph(&[(1, 2), (3, 4)]);
+        assert!(!split.is_connected());
+    }
+
+    #[test]
+    fn weak_components_ignore_direction() {
+        let graph = bigraph(&[(1, 2), (3, 2), (4, 5)]);
+        let mut components = graph.weakly_connected_components();
+        components.iter_mut().for_each(|component| component.sort());
+        components.sort();
+        assert_eq!(components, vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn eulerian_path_uses_every_edge_once() {
+        let edges = [(1, 2), (2, 3), (3, 1), (3, 4)];
+        let graph = bigraph(&edges);
+        let path = graph.eulerian_path().unwrap();
+        assert_eq!(path.len(), edges.len() + 1);
+
+        let mut used: Vec<(u32, u32)> = path
+            .windows(2)
+            .map(|pair| (pair[0].min(pair[1]), pair[0].max(pair[1])))
+            .collect();
+        used.sort();
+        let mut expected = edges.map(|(start, end)| (start.min(end), start.max(end)));
+        expected.sort();
+        assert_eq!(used, expected);
+
+        let star = bigraph(&[(1, 2), (1, 3), (1, 4)]);
+        assert_eq!(star.eulerian_path(), None);
+    }
+
+    #[test]
+    fn hamiltonian_path_on_path_and_star() {
+        let graph = bigraph(&[(2, 1), (3, 4), (1, 3)]);
+        assert_eq!(graph.hamiltonian_path(), Some(vec![2, 1, 3, 4]));
+
+        let against = bigraph(&[(1, 2), (3, 2)]);
+        assert_eq!(against.hamiltonian_path(), None);
+
+        let star = bigraph(&[(1, 2), (1, 3), (1, 4)]);
+        assert_eq!(star.hamiltonian_path(), None);
+    }
+
+    #[test]
+    fn all_paths_from_ingoing_vertex_is_an_error() {
+        let graph = bigraph(&[(1, 2)]);
+        graph.vertices.search(2).unwrap().borrow_mut().vicinity = Vicinity::Ingoing { edges: None };
+
+        assert!(matches!(
+            graph.all_paths_from(1),
+            Err(Error::MismatchedVicinity)
+        ));
+        assert!(matches!(
+            graph.all_paths_from(2),
+            Err(Error::MismatchedVicinity)
+        ));
+    }
+
+    #[test]
+    fn weighted_degrees_sum_edge_weights() {
+        let mut graph = Graph::<(), u32, u32, WithBoth>::default();
+        for id in [1, 2, 3] {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            graph.add_vertex(id, (), vicinity).unwrap();
+        }
+        for (start, end, weight) in [(1, 2, 4), (1, 3, 6), (3, 2, 5)] {
+            graph.add_edge(weight, start, end).unwrap();
+        }
+
+        assert_eq!(graph.weighted_out_degree(1).unwrap(), 10);
+        assert_eq!(graph.weighted_in_degree(2).unwrap(), 9);
+        assert_eq!(graph.weighted_in_degree(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn complement_of_a_path() {
+        let path = weighted_bigraph(&[(1, 2, 0), (2, 3, 0)]);
+        let complement = path.complement(|start, end| start * 10 + end);
+
+        assert_eq!(complement.vertex_count(), 3);
+        assert_eq!(
+            complement.edges_iter().collect::<Vec<_>>(),
+            vec![(1, 3, 13)]
+        );
+    }
+
+    #[test]
+    fn degree_histograms_of_a_star() {
+        let star = bigraph(&[(1, 2), (1, 3), (1, 4), (1, 5)]);
+
+        assert_eq!(star.degree_histogram(), BTreeMap::from([(1, 4), (4, 1)]));
+        assert_eq!(
+            star.out_degree_histogram(),
+            BTreeMap::from([(0, 4), (4, 1)])
+        );
+        assert_eq!(star.in_degree_histogram(), BTreeMap::from([(0, 1), (1, 4)]));
+    }
+
+    #[test]
+    fn stream_dot_covers_every_component() {
+        let graph = bigraph(&[(1, 2), (3, 4)]);
+        let mut out: Vec<u8> = Vec::default();
+        graph.stream_dot(&mut out).unwrap();
+        let dot = String::from_utf8(out).unwrap();
+
+        assert!(dot.contains("  \"1\" -> \"2\";\n"));
+        assert!(dot.contains("  \"3\" -> \"4\";\n"));
+        assert_eq!(dot.matches("->").count(), 2);
+    }
+
+    #[test]
+    fn topological_sort_on_both_vicinity() {
+        let mut graph = bigraph(&[(1, 2), (1, 3), (3, 2)]);
+        assert_eq!(
+            graph.topological_sort(1).unwrap(),
+            VecDeque::from([1, 3, 2])
+        );
+
+        graph.add_edge((), 2, 1).unwrap();
+        assert!(matches!(
+            graph.topological_sort(1),
+            Err(Error::WithMessage(_))
+        ));
+    }
+
+    struct Reverse;
+
+    impl Comparator<u32> for Reverse {
+        fn compare(lhs: &u32, rhs: &u32) -> Ordering {
+            rhs.cmp(lhs)
+        }
+    }
+
+    fn components<C: Comparator<u32>>(mut graph: Graph<(), (), u32, WithBoth, C>) -> Vec<usize> {
+        for id in 1..=6 {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            graph.add_vertex(id, (), vicinity).unwrap();
+        }
+        for (start, end) in [(1, 2), (3, 2), (4, 5)] {
+            graph.add_edge((), start, end).unwrap();
+        }
+        graph.component_sizes()
+    }
+
+    #[test]
+    fn component_sizes_are_sorted_descending() {
+        assert_eq!(components(Bigraph::new()), vec![3, 2, 1]);
+        assert_eq!(
+            components(Bigraph::with_comparator::<Reverse>()),
+            vec![3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn topological_sort_skips_unreachable_predecessors() {
+        let graph = bigraph(&[(0, 1), (1, 2), (1, 3), (3, 2), (4, 3)]);
+
+        assert_eq!(graph.topological_sort(1).unwrap(), vec![1, 3, 2]);
+        assert_eq!(graph.topological_sort(2).unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn incident_edges_cover_both_directions() {
+        let mut graph = Graph::<(), char, u32, WithBoth>::new();
+        for id in [1, 2, 3] {
+            let vicinity = Vicinity::Both {
+                ingoing_edges: None,
+                outgoing_edges: None,
+            };
+            graph.add_vertex(id, (), vicinity).unwrap();
+        }
+        for (start, end, info) in [(1, 2, 'a'), (2, 3, 'b'), (1, 3, 'c')] {
+            graph.add_edge(info, start, end).unwrap();
+        }
+
+        assert_eq!(
+            graph.incident_edges(2).unwrap(),
+            vec![(1, 2, 'a'), (2, 3, 'b')]
+        );
+        assert!(matches!(
+            graph.incident_edges(4),
+            Err(Error::KeyWasNotFound)
+        ));
+    }
+
+    #[test]
+    fn get_or_insert_vertex_keeps_existing_info() {
+        let mut graph: Graph<&str, (), u32, WithBoth> = Graph::new();
+        graph.get_or_insert_vertex(1, || "first").unwrap();
+        graph.get_or_insert_vertex(1, || "second").unwrap();
+
+        assert_eq!(graph.get_vertex_info(1).unwrap(), "first");
+    }
+
+    #[test]
+    fn schedule_levels_use_longest_path() {
+        let graph = bigraph(&[(1, 2), (1, 3), (2, 4), (3, 4), (1, 4)]);
+        let levels = graph.schedule_levels().unwrap();
+
+        assert_eq!(levels[&1], 0);
+        assert_eq!((levels[&2], levels[&3]), (1, 1));
+        assert_eq!(levels[&4], 2);
+
+        let cycle = bigraph(&[(1, 2), (2, 1)]);
+        assert!(cycle.schedule_levels().is_err());
+    }
+
+    #[test]
+    fn from_edges_strict_accepts_self_loops() {
+        let graph = Bigraph::from_edges_strict([(1, 1, ()), (1, 2, ())]).unwrap();
+        assert!(graph.has_edge(1, 1));
+        assert_eq!(graph.incident_edges(1).unwrap().len(), 3);
+        assert!(matches!(
+            Bigraph::from_edges_strict([(1, 1, ()), (1, 1, ())]),
+            Err(Error::EdgeAlreadyExists)
+        ));
+    }
+
+    #[test]
+    fn component_subgraph_excludes_other_components() {
+        let graph =
+            Bigraph::from_edges_strict([(1, 2, ()), (3, 2, ()), (4, 5, ()), (5, 6, ())]).unwrap();
+        let component = graph.component_subgraph(3).unwrap();
+
+        assert!(component.is_connected());
+        assert_eq!(component.vertex_count(), 3);
+        assert_eq!(
+            component.edges_iter().collect::<Vec<_>>(),
+            vec![(1, 2, ()), (3, 2, ())]
+        );
+        assert!(matches!(
+            graph.component_subgraph(7),
+            Err(Error::KeyWasNotFound)
+        ));
+    }
+
+    #[test]
+    fn weighted_diameter_of_a_path() {
+        let mut graph =
+            Graph::<(), u32, u32, WithBoth>::from_edges_strict([(1, 2, 3), (2, 3, 4), (3, 4, 5)])
+                .unwrap();
+
+        assert_eq!(graph.weighted_eccentricity(2).unwrap(), Some(9));
+        assert_eq!(graph.weighted_diameter(), Some(12));
+
+        graph
+            .add_vertex(
+                5,
+                (),
+                Vicinity::Both {
+                    ingoing_edges: None,
+                    outgoing_edges: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(graph.weighted_diameter(), None);
+    }
+
+    #[test]
+    fn flip_edge_reverses_one_edge() {
+        let mut graph =
+            Graph::<(), char, u32, WithBoth>::from_edges_strict([(1, 2, 'a'), (2, 3, 'b')])
+                .unwrap();
+        graph.flip_edge(1, 2).unwrap();
+
+        assert!(graph.has_edge(2, 1));
+        assert!(!graph.has_edge(1, 2));
+        assert!(graph.has_edge(2, 3));
+        assert_eq!(graph.incident_edges(1).unwrap(), vec![(2, 1, 'a')]);
+        assert!(matches!(graph.flip_edge(1, 2), Err(Error::EdgeWasNotFound)));
+    }
+
+    #[test]
+    fn flip_edge_drops_the_same_parallel_edge_from_both_lists() {
+        let mut graph = Graph::<(), char, u32, WithBoth>::from_edges_strict([(1, 2, 'a')]).unwrap();
+        graph.add_edge('b', 1, 2).unwrap();
+        graph
+            .with_vertex_mut(2, |vertex| {
+                if let Vicinity::Both {
+                    ingoing_edges: Some(edges),
+                    outgoing_edges: _,
+                } = &mut vertex.vicinity
+                {
+                    edges.reverse();
+                }
+            })
+            .unwrap();
+
+        graph.flip_edge(1, 2).unwrap();
+        assert_eq!(
+            graph.incident_edges(1).unwrap(),
+            vec![(2, 1, 'a'), (1, 2, 'b')]
+        );
+        assert_eq!(
+            graph.incident_edges(2).unwrap(),
+            vec![(1, 2, 'b'), (2, 1, 'a')]
+        );
+    }
+
+    #[test]
+    fn common_ancestors_of_diamond_branches() {
+        let graph =
+            Bigraph::from_edges_strict([(1, 2, ()), (1, 3, ()), (2, 4, ()), (3, 4, ())]).unwrap();
+
+        assert_eq!(graph.common_ancestors(2, 3).unwrap(), HashSet::from([1]));
+        assert_eq!(graph.common_ancestors(4, 2).unwrap(), HashSet::from([1]));
+        assert!(graph.common_ancestors(1, 4).unwrap().is_empty());
+        assert!(matches!(
+            graph.common_ancestors(2, 5),
+            Err(Error::KeyWasNotFound)
+        ));
+    }
+
+    #[test]
+    fn lowest_common_ancestors_unique_and_criss_cross() {
+        let tree = Bigraph::from_edges_strict([(1, 2, ()), (2, 3, ()), (2, 4, ())]).unwrap();
+        assert_eq!(tree.lowest_common_ancestors(3, 4).unwrap(), vec![2]);
+
+        let criss_cross = Bigraph::from_edges_strict([
+            (0, 1, ()),
+            (0, 2, ()),
+            (1, 3, ()),
+            (1, 4, ()),
+            (2, 3, ()),
+            (2, 4, ()),
+        ])
+        .unwrap();
+        assert_eq!(
+            criss_cross.lowest_common_ancestors(3, 4).unwrap(),
+            vec![1, 2]
+        );
+    }
 }
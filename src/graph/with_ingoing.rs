@@ -1,14 +1,19 @@
-use std::cell::RefCell;
-pub use super::{definitions::{Vicinity, WithIngoing, Vertex}, Graph};
+use std::{
+    cell::RefCell,
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+};
+pub use super::{definitions::{Vicinity, WithIngoing, Vertex}, Comparator, Graph};
 use crate::Error;
 
 
 #[allow(dead_code)]
-impl<V, E, Id> Graph<V, E, Id, WithIngoing>
+impl<V, E, Id, C> Graph<V, E, Id, WithIngoing, C>
 where
     V: Clone,
     E: Clone,
     Id: PartialOrd + Ord + PartialEq + Eq + Copy,
+    C: Comparator<Id>,
 {
     pub fn add_vertex(
         &mut self,
@@ -25,4 +30,81 @@ where
         self.vertices.insert(id, RefCell::new(vertex).into())?;
         Ok(())
     }
+
+    /// Returns the id of the first vertex whose vicinity does not match the
+    /// graph's state, paired with `Error::MismatchedVicinity`.
+    pub fn validate(&self) -> Result<(), (Id, Error)> {
+        for (id, vertex) in self.vertices.into_vec() {
+            if !matches!(&vertex.borrow().vicinity, Vicinity::Ingoing { edges: _ }) {
+                return Err((id, Error::MismatchedVicinity));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn ancestors(&self, id: Id) -> Result<HashSet<Id>, Error>
+    where
+        Id: Hash,
+    {
+        if !self.vertices.contains(id) {
+            return Err(Error::KeyWasNotFound);
+        }
+
+        let mut ancestors: HashSet<Id> = HashSet::default();
+        let mut queue: VecDeque<Id> = VecDeque::from([id]);
+
+        while let Some(current) = queue.pop_front() {
+            let vertex = self.vertices.search(current)?.borrow();
+            if let Vicinity::Ingoing { edges: Some(edges) } = &vertex.vicinity {
+                for edge in edges {
+                    let Some(binding) = edge.start.0.upgrade() else {
+                        continue;
+                    };
+                    let start_id = binding.borrow().id;
+                    if start_id != id && ancestors.insert(start_id) {
+                        queue.push_back(start_id);
+                    }
+                }
+            }
+        }
+        Ok(ancestors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ancestors_follow_ingoing_edges() {
+        let mut graph = Graph::<(), (), u32, WithIngoing>::default();
+        for id in 1..=3 {
+            graph
+                .add_vertex(id, (), Vicinity::Ingoing { edges: None })
+                .unwrap();
+        }
+        graph.add_edge((), 1, 2).unwrap();
+        graph.add_edge((), 2, 3).unwrap();
+        assert_eq!(graph.ancestors(3).unwrap(), HashSet::from([1, 2]));
+        assert!(matches!(graph.ancestors(9), Err(Error::KeyWasNotFound)));
+    }
+
+    #[test]
+    fn validate_reports_offending_vertex() {
+        let mut graph: Graph<(), (), u32, WithIngoing> = Graph::new();
+        graph
+            .add_vertex(1, (), Vicinity::Ingoing { edges: None })
+            .unwrap();
+        assert!(graph.validate().is_ok());
+
+        let vertex = Vertex::new(2, (), Vicinity::Outgoing { edges: None });
+        graph
+            .vertices
+            .insert(2, RefCell::new(vertex).into())
+            .unwrap();
+        assert!(matches!(
+            graph.validate(),
+            Err((2, Error::MismatchedVicinity))
+        ));
+    }
 }
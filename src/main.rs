@@ -35,24 +35,15 @@ fn main() -> anyhow::Result<()> {
     current_id += 1;
 
     while !task_stack.is_empty() {
-        let prev_task = task_stack
-            .get(task_stack.len() - 1)
-            .ok_or(Error::UnexpectedError)?;
+        let prev_task = task_stack.back().ok_or(Error::UnexpectedError)?;
         println!("Current task is: {}", &prev_task.task);
         println!("Does that depends on another task?");
 
         let mut depends = String::default();
         io::stdin().read_line(&mut depends)?;
-        let depends: bool = {
-            if depends.to_lowercase().eq("yes\n")
-                || depends.to_lowercase().eq("true\n")
-                || depends.to_lowercase().eq("y\n")
-            {
-                true
-            } else {
-                false
-            }
-        };
+        let depends: bool = depends.to_lowercase().eq("yes\n")
+            || depends.to_lowercase().eq("true\n")
+            || depends.to_lowercase().eq("y\n");
 
         if !depends {
             let curr_task = task_stack.pop_back().ok_or(Error::UnexpectedError)?;